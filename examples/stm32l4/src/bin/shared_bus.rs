@@ -0,0 +1,79 @@
+/*
+Share the I2C bus between the Akafugu TWIDisplay and a DS3231 RTC:
+read the time from the RTC and display it
+*/
+
+#![no_main]
+#![no_std]
+
+use cortex_m;
+use cortex_m_rt::entry;
+use panic_halt as _;
+use stm32l4xx_hal::{
+    delay::Delay,
+    prelude::*,
+    i2c::I2c,
+    };
+
+use akafugu_twidisplay::*;
+
+// maximum time to wait for the display to answer after boot
+const BOOT_TIMEOUT_MS: u32 = 500;
+
+const RTC_ADDRESS: u8 = 0x68;
+
+#[entry]
+fn main() -> ! {
+    let cp = cortex_m::Peripherals::take().unwrap();
+    let dp = stm32l4xx_hal::stm32::Peripherals::take().unwrap();
+
+    let mut flash = dp.FLASH.constrain();
+    let mut rcc = dp.RCC.constrain();
+    let mut pwr = dp.PWR.constrain(&mut rcc.apb1r1);
+
+    let clocks = rcc.cfgr.freeze(&mut flash.acr, &mut pwr);
+
+    let mut gpioa = dp.GPIOA.split(&mut rcc.ahb2);
+
+    let mut delay = Delay::new(cp.SYST, clocks);
+
+    let mut scl = gpioa.pa9.into_open_drain_output(&mut gpioa.moder, &mut gpioa.otyper);
+
+    scl.internal_pull_up(&mut gpioa.pupdr, true);
+    let scl = scl.into_af4(&mut gpioa.moder, &mut gpioa.afrh);
+
+    let mut sda = gpioa.pa10.into_open_drain_output(&mut gpioa.moder, &mut gpioa.otyper);
+    sda.internal_pull_up(&mut gpioa.pupdr, true);
+    let sda = sda.into_af4(&mut gpioa.moder, &mut gpioa.afrh);
+
+    let mut i2c = I2c::i2c1(dp.I2C1, (scl, sda), 100.khz(), clocks, &mut rcc.apb1r1);
+
+    // the display only borrows the bus for the setup
+    // wait until the display answers, instead of a fixed delay on boot
+    let mut akafugu = TWIDisplay::new(I2cRef::new(&mut i2c), DEFAULT_ADDRESS);
+    akafugu.wait_ready(&mut delay, BOOT_TIMEOUT_MS).unwrap();
+    akafugu.clear_display().unwrap();
+    akafugu.set_brightness(127).unwrap();
+    akafugu.destroy();
+
+    loop {
+
+        // read seconds, minutes and hours (BCD) from the RTC, using the same bus
+        let mut data = [0u8; 3];
+        i2c.write_read(RTC_ADDRESS, &[0x00], &mut data).unwrap();
+
+        let seconds = (data[0] >> 4) * 10 + (data[0] & 0x0f);
+        let minutes = (data[1] >> 4) * 10 + (data[1] & 0x0f);
+        let hours = ((data[2] >> 4) & 0x03) * 10 + (data[2] & 0x0f);
+
+        // borrow the bus again to show the time, dot blinking every second
+        let mut akafugu = TWIDisplay::new(I2cRef::new(&mut i2c), DEFAULT_ADDRESS);
+        akafugu.display_time(hours, minutes, seconds % 2 == 0).unwrap();
+        akafugu.destroy();
+
+        delay.delay_ms(500_u32);
+
+        }
+
+
+}
@@ -16,7 +16,8 @@ use stm32l4xx_hal::{
 
 use akafugu_twidisplay::*;
 
-const BOOT_DELAY_MS: u16 = 100; 
+// maximum time to wait for the display to answer after boot
+const BOOT_TIMEOUT_MS: u32 = 500;
 
 #[entry]
 fn main() -> ! {
@@ -36,9 +37,6 @@ fn main() -> ! {
     
     let mut delay = Delay::new(cp.SYST, clocks);
 
-    // delay necessary for the I2C to initiate correctly and start on boot without having to reset the board
-    delay.delay_ms(BOOT_DELAY_MS);
-    
     let mut scl = gpioa.pa9.into_open_drain_output(&mut gpioa.moder, &mut gpioa.otyper);
     
     scl.internal_pull_up(&mut gpioa.pupdr, true);
@@ -50,9 +48,12 @@ fn main() -> ! {
 
     let mut i2c = I2c::i2c1(dp.I2C1, (scl, sda), 100.khz(), clocks, &mut rcc.apb1r1);
     
-    let mut akafugu = TWIDisplay::new(i2c, DEFAULT_ADDRESS);
-    
-    akafugu.clear_display().unwrap();
+    // wait for the display to boot, then clear it, set the mode and brightness in one go
+    let mut akafugu = TWIDisplayBuilder::new()
+        .mode(Mode::Rotate)
+        .brightness(127)
+        .init_when_ready(i2c, &mut delay, BOOT_TIMEOUT_MS)
+        .unwrap();
 
     akafugu.display_date(11,14, DateFormat::MMDD, true).unwrap();
 
@@ -0,0 +1,139 @@
+//! Looping frame animations, with built-in spinners and custom frames of characters and segment patterns
+
+use crate::hal::blocking::delay::DelayMs;
+use crate::hal::blocking::i2c::{Write, WriteRead};
+use crate::{Error, Segment, TWIDisplay};
+
+/// Content of one position in an animation frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Glyph {
+    /// Character, shown with `display_char()`
+    Char(char),
+    /// Segment pattern, shown with `display_segments()` (experimental)
+    Segments(u8),
+}
+
+/// Content of the four positions
+pub type Frame = [Glyph; 4];
+
+const BLANK: Glyph = Glyph::Char(' ');
+const DASH: Glyph = Glyph::Char('-');
+
+/// One segment rotating clockwise at every position (experimental, made of segment patterns)
+pub const SPINNER: [Frame; 6] = [
+    [Glyph::Segments(Segment::A); 4],
+    [Glyph::Segments(Segment::B); 4],
+    [Glyph::Segments(Segment::C); 4],
+    [Glyph::Segments(Segment::D); 4],
+    [Glyph::Segments(Segment::E); 4],
+    [Glyph::Segments(Segment::F); 4],
+];
+
+/// Dash walking from left to right
+pub const WALKING_DASH: [Frame; 4] = [
+    [DASH, BLANK, BLANK, BLANK],
+    [BLANK, DASH, BLANK, BLANK],
+    [BLANK, BLANK, DASH, BLANK],
+    [BLANK, BLANK, BLANK, DASH],
+];
+
+/// One segment running clockwise around the edge of the display (experimental, made of segment patterns)
+pub const PERIMETER: [Frame; 12] = [
+    [Glyph::Segments(Segment::A), BLANK, BLANK, BLANK],
+    [BLANK, Glyph::Segments(Segment::A), BLANK, BLANK],
+    [BLANK, BLANK, Glyph::Segments(Segment::A), BLANK],
+    [BLANK, BLANK, BLANK, Glyph::Segments(Segment::A)],
+    [BLANK, BLANK, BLANK, Glyph::Segments(Segment::B)],
+    [BLANK, BLANK, BLANK, Glyph::Segments(Segment::C)],
+    [BLANK, BLANK, BLANK, Glyph::Segments(Segment::D)],
+    [BLANK, BLANK, Glyph::Segments(Segment::D), BLANK],
+    [BLANK, Glyph::Segments(Segment::D), BLANK, BLANK],
+    [Glyph::Segments(Segment::D), BLANK, BLANK, BLANK],
+    [Glyph::Segments(Segment::E), BLANK, BLANK, BLANK],
+    [Glyph::Segments(Segment::F), BLANK, BLANK, BLANK],
+];
+
+/// Looping sequence of frames, each shown for the same time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Animation<'a> {
+    frames: &'a [Frame],
+    frame_ms: u32,
+    elapsed_ms: u32,
+    index: usize,
+    shown: bool,
+}
+
+impl<'a> Animation<'a> {
+    /// Create a new animation from the frames (built-in or custom), each shown for `frame_ms` milliseconds
+    pub fn new(frames: &'a [Frame], frame_ms: u32) -> Self {
+        Animation {
+            frames,
+            frame_ms: frame_ms.max(1),
+            elapsed_ms: 0,
+            index: 0,
+            shown: false,
+        }
+    }
+
+    /// Index of the current frame
+    pub fn frame(&self) -> usize {
+        self.index
+    }
+
+    /// Go back to the first frame
+    pub fn reset(&mut self) {
+        self.elapsed_ms = 0;
+        self.index = 0;
+        self.shown = false;
+    }
+
+    /// Move the animation forward by `elapsed_ms` milliseconds, showing the next frame when due.
+    /// The first call shows the first frame.
+    pub fn update<I2C, D, E>(
+        &mut self,
+        display: &mut TWIDisplay<I2C, D>,
+        elapsed_ms: u32,
+    ) -> Result<(), Error<E>>
+    where
+        I2C: Write<Error = E> + WriteRead<Error = E>,
+        D: DelayMs<u16>,
+    {
+        if self.frames.is_empty() {
+            return Ok(());
+        }
+
+        if self.shown {
+            self.elapsed_ms = self.elapsed_ms.saturating_add(elapsed_ms);
+            if self.elapsed_ms < self.frame_ms {
+                return Ok(());
+            }
+            let steps = (self.elapsed_ms / self.frame_ms) as usize;
+            self.elapsed_ms %= self.frame_ms;
+            self.index = (self.index + steps) % self.frames.len();
+        }
+
+        self.render(display)
+    }
+
+    /// Show the current frame
+    pub fn render<I2C, D, E>(&mut self, display: &mut TWIDisplay<I2C, D>) -> Result<(), Error<E>>
+    where
+        I2C: Write<Error = E> + WriteRead<Error = E>,
+        D: DelayMs<u16>,
+    {
+        let frame = match self.frames.get(self.index) {
+            Some(frame) => frame,
+            None => return Ok(()),
+        };
+
+        for (pos, glyph) in frame.iter().enumerate() {
+            match *glyph {
+                Glyph::Char(ch) => display.display_char(pos as u8, ch)?,
+                Glyph::Segments(segments) => display.display_segments(pos as u8, segments)?,
+            }
+        }
+
+        self.shown = true;
+        Ok(())
+    }
+}
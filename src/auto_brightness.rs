@@ -0,0 +1,136 @@
+//! Brightness following the ambient light
+
+use crate::hal::blocking::delay::DelayMs;
+use crate::hal::blocking::i2c::{Write, WriteRead};
+use crate::{Error, TWIDisplay};
+
+/// Ambient light sensor, to be implemented for the sensor driver in use
+pub trait LightSensor {
+    /// Read the illuminance in lux, `None` if no reading is available
+    fn lux(&mut self) -> Option<u32>;
+}
+
+/// Controller mapping illuminance readings to display brightness.
+///
+/// The curve is a list of `(lux, brightness)` points sorted by lux, with linear interpolation between them.
+/// Target changes smaller than the hysteresis are ignored, and the brightness moves towards
+/// the target by at most `max_step` per update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AutoBrightness<'a> {
+    curve: &'a [(u32, u8)],
+    hysteresis: u8,
+    max_step: u8,
+    target: Option<u8>,
+    current: Option<u8>,
+}
+
+impl<'a> AutoBrightness<'a> {
+    /// Create a new controller with the given curve, hysteresis of 8 and maximum step of 16
+    pub fn new(curve: &'a [(u32, u8)]) -> Self {
+        AutoBrightness {
+            curve,
+            hysteresis: 8,
+            max_step: 16,
+            target: None,
+            current: None,
+        }
+    }
+
+    /// Set the smallest change of the target brightness that is followed
+    pub fn hysteresis(mut self, hysteresis: u8) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// Set the largest brightness change per update (0 means no limit)
+    pub fn max_step(mut self, max_step: u8) -> Self {
+        self.max_step = max_step;
+        self
+    }
+
+    /// Brightness the controller is moving towards, `None` before the first reading
+    pub fn target(&self) -> Option<u8> {
+        self.target
+    }
+
+    /// Brightness given by the curve for an illuminance (255 if the curve is empty)
+    pub fn brightness_for(&self, lux: u32) -> u8 {
+        let (first, last) = match (self.curve.first(), self.curve.last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => return 255,
+        };
+
+        if lux <= first.0 {
+            return first.1;
+        }
+        if lux >= last.0 {
+            return last.1;
+        }
+
+        for pair in self.curve.windows(2) {
+            let (lux_lo, b_lo) = pair[0];
+            let (lux_hi, b_hi) = pair[1];
+            if lux >= lux_lo && lux < lux_hi {
+                let span = (lux_hi - lux_lo) as i64;
+                let delta = b_hi as i64 - b_lo as i64;
+                return (b_lo as i64 + delta * (lux - lux_lo) as i64 / span) as u8;
+            }
+        }
+
+        last.1
+    }
+
+    /// Process a reading and return the brightness to set now
+    pub fn process(&mut self, lux: u32) -> u8 {
+        let new_target = self.brightness_for(lux);
+
+        let target = match self.target {
+            Some(t) if t.abs_diff(new_target) <= self.hysteresis => t,
+            _ => new_target,
+        };
+        self.target = Some(target);
+
+        let current = match self.current {
+            None => target,
+            Some(c) if self.max_step == 0 || c.abs_diff(target) <= self.max_step => target,
+            Some(c) if c < target => c + self.max_step,
+            Some(c) => c - self.max_step,
+        };
+        self.current = Some(current);
+
+        current
+    }
+
+    /// Process a reading and set the display brightness, return the brightness set
+    pub fn update<I2C, D, E>(
+        &mut self,
+        display: &mut TWIDisplay<I2C, D>,
+        lux: u32,
+    ) -> Result<u8, Error<E>>
+    where
+        I2C: Write<Error = E> + WriteRead<Error = E>,
+        D: DelayMs<u16>,
+    {
+        let brightness = self.process(lux);
+        display.set_brightness(brightness)?;
+        Ok(brightness)
+    }
+
+    /// Read the sensor and set the display brightness, return the brightness set.
+    /// Without a reading the brightness is not changed and `None` is returned.
+    pub fn update_from<I2C, D, E, S>(
+        &mut self,
+        display: &mut TWIDisplay<I2C, D>,
+        sensor: &mut S,
+    ) -> Result<Option<u8>, Error<E>>
+    where
+        I2C: Write<Error = E> + WriteRead<Error = E>,
+        D: DelayMs<u16>,
+        S: LightSensor,
+    {
+        match sensor.lux() {
+            Some(lux) => self.update(display, lux).map(Some),
+            None => Ok(None),
+        }
+    }
+}
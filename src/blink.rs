@@ -0,0 +1,147 @@
+//! Software blink of selected positions and dots, hiding and restoring the cached content
+
+use crate::hal::blocking::delay::DelayMs;
+use crate::hal::blocking::i2c::{Write, WriteRead};
+use crate::{dot_mask, Cell, Error, TWIDisplay, BLANK};
+
+/// Blinks the selected positions and dots, keeping the content underneath.
+///
+/// While hidden, the selected positions are blank and the selected dots are off;
+/// the content is restored when they are shown again. Content written to a hidden position
+/// shows up immediately and is kept. Positions whose content the driver doesn't know
+/// (e.g. after `send_text()` or `display_address()`) are not blinked, so nothing is lost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Blink {
+    positions: [bool; 4],
+    dots: u8,
+    half_period_ms: u32,
+    elapsed_ms: u32,
+    hidden: bool,
+    saved_cells: [Option<Cell>; 4],
+    saved_dots: Option<u8>,
+}
+
+impl Blink {
+    /// Create a new blink for the selected positions and dots, with a full on-off cycle of `period_ms` milliseconds
+    pub fn new(positions: [bool; 4], dots: [bool; 4], period_ms: u32) -> Self {
+        Blink {
+            positions,
+            dots: dot_mask(dots),
+            half_period_ms: (period_ms / 2).max(1),
+            elapsed_ms: 0,
+            hidden: false,
+            saved_cells: [None; 4],
+            saved_dots: None,
+        }
+    }
+
+    /// Check if the selected positions are currently hidden
+    pub fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    /// Move the blink forward by `elapsed_ms` milliseconds, hiding or showing the selection when due
+    pub fn update<I2C, D, E>(
+        &mut self,
+        display: &mut TWIDisplay<I2C, D>,
+        elapsed_ms: u32,
+    ) -> Result<(), Error<E>>
+    where
+        I2C: Write<Error = E> + WriteRead<Error = E>,
+        D: DelayMs<u16>,
+    {
+        self.elapsed_ms = self.elapsed_ms.saturating_add(elapsed_ms);
+        if self.elapsed_ms < self.half_period_ms {
+            return Ok(());
+        }
+        self.elapsed_ms %= self.half_period_ms;
+
+        if self.hidden {
+            self.show(display)
+        } else {
+            self.hide(display)
+        }
+    }
+
+    /// Select other positions and dots, showing the previous selection first
+    pub fn select<I2C, D, E>(
+        &mut self,
+        display: &mut TWIDisplay<I2C, D>,
+        positions: [bool; 4],
+        dots: [bool; 4],
+    ) -> Result<(), Error<E>>
+    where
+        I2C: Write<Error = E> + WriteRead<Error = E>,
+        D: DelayMs<u16>,
+    {
+        self.stop(display)?;
+        self.positions = positions;
+        self.dots = dot_mask(dots);
+        Ok(())
+    }
+
+    /// Show the selection and restart the cycle
+    pub fn stop<I2C, D, E>(&mut self, display: &mut TWIDisplay<I2C, D>) -> Result<(), Error<E>>
+    where
+        I2C: Write<Error = E> + WriteRead<Error = E>,
+        D: DelayMs<u16>,
+    {
+        self.elapsed_ms = 0;
+        if self.hidden {
+            self.show(display)?;
+        }
+        Ok(())
+    }
+
+    fn hide<I2C, D, E>(&mut self, display: &mut TWIDisplay<I2C, D>) -> Result<(), Error<E>>
+    where
+        I2C: Write<Error = E> + WriteRead<Error = E>,
+        D: DelayMs<u16>,
+    {
+        self.hidden = true;
+
+        for (pos, selected) in self.positions.iter().enumerate() {
+            if !*selected {
+                continue;
+            }
+            // content unknown to the driver (e.g. after `send_text()`) could not be restored
+            if let Some(value) = display.cell(pos as u8) {
+                self.saved_cells[pos] = Some(value);
+                display.write_cell(pos as u8, BLANK)?;
+            }
+        }
+
+        self.saved_dots = display.dots_register();
+        if let Some(dots) = self.saved_dots {
+            display.write_dots(dots & !self.dots)?;
+        } else if self.dots != 0 {
+            // state of the other dots unknown: the selected ones are switched off with them
+            display.write_dots(0)?;
+        }
+
+        Ok(())
+    }
+
+    fn show<I2C, D, E>(&mut self, display: &mut TWIDisplay<I2C, D>) -> Result<(), Error<E>>
+    where
+        I2C: Write<Error = E> + WriteRead<Error = E>,
+        D: DelayMs<u16>,
+    {
+        self.hidden = false;
+
+        for (pos, selected) in self.positions.iter().enumerate() {
+            // positions written while hidden keep their new content
+            if *selected && display.cell(pos as u8) == Some(BLANK) {
+                if let Some(value) = self.saved_cells[pos].take() {
+                    display.write_cell(pos as u8, value)?;
+                }
+            }
+        }
+
+        if let (Some(saved), Some(current)) = (self.saved_dots.take(), display.dots_register()) {
+            display.write_dots(current | (saved & self.dots))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+//! Configuration of the display applied in one go
+
+use crate::hal::blocking::delay::DelayMs;
+use crate::hal::blocking::i2c::{Write, WriteRead};
+use crate::{Error, Mode, TWIDisplay, DEFAULT_ADDRESS};
+
+/// Builder collecting the settings of the display, applied by `init()`
+#[derive(Debug, Clone, Copy)]
+pub struct TWIDisplayBuilder<'a> {
+    address: u8,
+    brightness: Option<u8>,
+    mode: Option<Mode>,
+    digits: Option<u8>,
+    text: Option<&'a str>,
+}
+
+impl<'a> Default for TWIDisplayBuilder<'a> {
+    fn default() -> Self {
+        TWIDisplayBuilder {
+            address: DEFAULT_ADDRESS,
+            brightness: None,
+            mode: None,
+            digits: None,
+            text: None,
+        }
+    }
+}
+
+impl<'a> TWIDisplayBuilder<'a> {
+    /// Create a new builder for a display at the default address
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// I2C address of the display, defaults to 0x12
+    pub fn address(mut self, address: u8) -> Self {
+        self.address = address;
+        self
+    }
+
+    /// Brightness set on initialization (0 - 255, 127 is 50%)
+    pub fn brightness(mut self, brightness: u8) -> Self {
+        self.brightness = Some(brightness);
+        self
+    }
+
+    /// Display mode set on initialization
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Expected number of digits: on initialization it is compared with the number reported by the display.
+    ///
+    /// __NOTE:__ this reads from the display (`get_number_digits()`), which has not been verified on hardware yet;
+    /// leave it unset if initialization fails with a bus error.
+    pub fn digits(mut self, digits: u8) -> Self {
+        self.digits = Some(digits);
+        self
+    }
+
+    /// Text shown from the leftmost position after initialization (up to 4 characters)
+    pub fn text(mut self, text: &'a str) -> Self {
+        self.text = Some(text);
+        self
+    }
+
+    /// Create the driver and apply the settings: clear the display, then set the mode,
+    /// the brightness and show the startup text.
+    pub fn init<I2C, E>(self, i2c: I2C) -> Result<TWIDisplay<I2C>, Error<E>>
+    where
+        I2C: Write<Error = E> + WriteRead<Error = E>,
+    {
+        self.check()?;
+        self.apply(TWIDisplay::new(i2c, self.address))
+    }
+
+    /// Same as `init()`, but first wait until the display answers after boot
+    /// (see `TWIDisplay::wait_ready()`), for at most `timeout_ms` milliseconds.
+    pub fn init_when_ready<I2C, E, D>(
+        self,
+        i2c: I2C,
+        delay: &mut D,
+        timeout_ms: u32,
+    ) -> Result<TWIDisplay<I2C>, Error<E>>
+    where
+        I2C: Write<Error = E> + WriteRead<Error = E>,
+        D: DelayMs<u16>,
+    {
+        self.check()?;
+        let mut display = TWIDisplay::new(i2c, self.address);
+        display.wait_ready(delay, timeout_ms)?;
+        self.apply(display)
+    }
+
+    /// Check the settings before sending anything
+    fn check<E>(&self) -> Result<(), Error<E>> {
+        if let Some(text) = self.text {
+            if text.chars().count() > 4 {
+                return Err(Error::ValueOutOfRange);
+            }
+            if !text.is_ascii() {
+                return Err(Error::UnsupportedChar);
+            }
+        }
+        Ok(())
+    }
+
+    /// Send the settings to the display
+    fn apply<I2C, E>(self, mut display: TWIDisplay<I2C>) -> Result<TWIDisplay<I2C>, Error<E>>
+    where
+        I2C: Write<Error = E> + WriteRead<Error = E>,
+    {
+        if let Some(expected) = self.digits {
+            let digits = display.get_number_digits()?;
+            if digits != expected {
+                return Err(Error::UnexpectedDigitCount(digits));
+            }
+        }
+
+        display.clear_display()?;
+
+        if let Some(mode) = self.mode {
+            display.set_mode(mode)?;
+        }
+
+        if let Some(brightness) = self.brightness {
+            display.set_brightness(brightness)?;
+        }
+
+        if let Some(text) = self.text {
+            for (pos, ch) in text.chars().enumerate() {
+                display.display_char(pos as u8, ch)?
+            }
+        }
+
+        Ok(display)
+    }
+}
@@ -0,0 +1,68 @@
+//! Borrowing the I2C bus instead of owning it
+
+use crate::hal::blocking::i2c::{Read, Write, WriteRead};
+
+/// Mutable reference to an I2C bus, usable as the bus of a driver.
+///
+/// The driver only borrows the bus, so it can be shared with other devices
+/// by creating the driver when needed, or by destroying it:
+///
+/// ```no_run
+/// # use akafugu_twidisplay::*;
+/// # use embedded_hal::blocking::i2c::WriteRead;
+/// # let mut i2c = embedded_hal_mock::I2cMock::new();
+/// # const RTC_ADDRESS: u8 = 0x68;
+/// # let mut buffer = [0u8; 1];
+/// let mut display = TWIDisplay::new(I2cRef::new(&mut i2c), DEFAULT_ADDRESS);
+/// display.display_number(1234).unwrap();
+/// display.destroy();
+///
+/// // the bus is free again for the other devices
+/// i2c.write_read(RTC_ADDRESS, &[0x00], &mut buffer).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct I2cRef<'a, I2C> {
+    i2c: &'a mut I2C,
+}
+
+impl<'a, I2C> I2cRef<'a, I2C> {
+    /// Borrow the I2C bus
+    pub fn new(i2c: &'a mut I2C) -> Self {
+        I2cRef { i2c }
+    }
+}
+
+impl<'a, I2C> From<&'a mut I2C> for I2cRef<'a, I2C> {
+    fn from(i2c: &'a mut I2C) -> Self {
+        I2cRef::new(i2c)
+    }
+}
+
+impl<'a, I2C: Write> Write for I2cRef<'a, I2C> {
+    type Error = I2C::Error;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.i2c.write(address, bytes)
+    }
+}
+
+impl<'a, I2C: WriteRead> WriteRead for I2cRef<'a, I2C> {
+    type Error = I2C::Error;
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.i2c.write_read(address, bytes, buffer)
+    }
+}
+
+impl<'a, I2C: Read> Read for I2cRef<'a, I2C> {
+    type Error = I2C::Error;
+
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.i2c.read(address, buffer)
+    }
+}
@@ -0,0 +1,153 @@
+//! Several displays mounted side by side, used as one wide display
+
+use crate::hal::blocking::delay::DelayMs;
+use crate::hal::blocking::i2c::{Write, WriteRead};
+use crate::{Error, NoDelay, TWIDisplay};
+
+/// Number of digits of a single TWIDisplay
+const DIGITS_PER_DISPLAY: usize = 4;
+
+/// Chain of `N` displays, ordered from left to right, acting as one display with `4 * N` positions.
+///
+/// Each display has its own address; the displays can share one bus through
+/// a bus sharing proxy, with one proxy per display.
+#[derive(Debug)]
+pub struct DisplayChain<I2C, const N: usize, D = NoDelay> {
+    displays: [TWIDisplay<I2C, D>; N],
+}
+
+impl<I2C, E, const N: usize, D> DisplayChain<I2C, N, D>
+where
+    I2C: Write<Error = E> + WriteRead<Error = E>,
+    D: DelayMs<u16>,
+{
+    /// Create a new chain from displays ordered from left to right
+    pub fn new(displays: [TWIDisplay<I2C, D>; N]) -> Self {
+        DisplayChain { displays }
+    }
+
+    /// Destroy the chain, return the displays
+    pub fn destroy(self) -> [TWIDisplay<I2C, D>; N] {
+        self.displays
+    }
+
+    /// Get a single display of the chain (0 is the leftmost)
+    pub fn display(&mut self, index: usize) -> Option<&mut TWIDisplay<I2C, D>> {
+        self.displays.get_mut(index)
+    }
+
+    /// Number of positions of the whole chain
+    pub fn width(&self) -> usize {
+        DIGITS_PER_DISPLAY * N
+    }
+
+    /// Clear all the displays
+    pub fn clear_display(&mut self) -> Result<(), Error<E>> {
+        for display in self.displays.iter_mut() {
+            display.clear_display()?
+        }
+        Ok(())
+    }
+
+    /// Set brightness of all the displays (0 - 255, 127 is 50%)
+    pub fn set_brightness(&mut self, brightness: u8) -> Result<(), Error<E>> {
+        for display in self.displays.iter_mut() {
+            display.set_brightness(brightness)?
+        }
+        Ok(())
+    }
+
+    /// Write digit D at position P of the chain
+    pub fn display_digit(&mut self, position: usize, digit: u8) -> Result<(), Error<E>> {
+        let (display, pos) = self.locate(position)?;
+        display.display_digit(pos, digit)
+    }
+
+    /// Write character C at position P of the chain
+    pub fn display_char(&mut self, position: usize, ch: char) -> Result<(), Error<E>> {
+        let (display, pos) = self.locate(position)?;
+        display.display_char(pos, ch)
+    }
+
+    /// Display a number using all the digits of the chain, with leading zeros
+    pub fn display_number(&mut self, number: u64) -> Result<(), Error<E>> {
+        let width = self.width();
+
+        // numbers with more digits than the chain has positions don't fit
+        let fits = match 10u64.checked_pow(width as u32) {
+            Some(limit) => number < limit,
+            None => true,
+        };
+        if !fits {
+            return Err(Error::ValueOutOfRange);
+        }
+
+        let mut data = number;
+        for position in (0..width).rev() {
+            self.display_digit(position, (data % 10) as u8)?;
+            data /= 10;
+        }
+
+        Ok(())
+    }
+
+    /// Display text from the leftmost position, filling the rest with blanks.
+    /// Text longer than the chain returns `Error::ValueOutOfRange`.
+    pub fn display_text(&mut self, text: &str) -> Result<(), Error<E>> {
+        if text.chars().count() > self.width() {
+            return Err(Error::ValueOutOfRange);
+        }
+
+        let mut chars = text.chars();
+        for position in 0..self.width() {
+            self.display_char(position, chars.next().unwrap_or(' '))?
+        }
+
+        Ok(())
+    }
+
+    /// Display one step of text scrolling from right to left through the whole chain.
+    ///
+    /// The text is followed by a blank gap as wide as the chain, and `offset` wraps around,
+    /// so calling it with 0, 1, 2... on every tick scrolls the text continuously.
+    pub fn scroll_text(&mut self, text: &str, offset: usize) -> Result<(), Error<E>> {
+        let width = self.width();
+        let length = text.chars().count() + width;
+        let start = offset % length;
+
+        for position in 0..width {
+            let idx = (start + position) % length;
+            let ch = text.chars().nth(idx).unwrap_or(' ');
+            self.display_char(position, ch)?
+        }
+
+        Ok(())
+    }
+
+    /// Display the dots of the whole chain, one switch per position (true is on, false is off).
+    /// The number of switches must match the width of the chain, otherwise `Error::ValueOutOfRange` is returned.
+    pub fn display_dots(&mut self, dots: &[bool]) -> Result<(), Error<E>> {
+        if dots.len() != self.width() {
+            return Err(Error::ValueOutOfRange);
+        }
+
+        for (display, chunk) in self
+            .displays
+            .iter_mut()
+            .zip(dots.chunks(DIGITS_PER_DISPLAY))
+        {
+            display.display_dots([chunk[0], chunk[1], chunk[2], chunk[3]])?
+        }
+
+        Ok(())
+    }
+
+    /// Find the display and its local position for a position of the chain
+    fn locate(&mut self, position: usize) -> Result<(&mut TWIDisplay<I2C, D>, u8), Error<E>> {
+        let display = self
+            .displays
+            .get_mut(position / DIGITS_PER_DISPLAY)
+            .ok_or(Error::InvalidPosition)?;
+        Ok((display, (position % DIGITS_PER_DISPLAY) as u8))
+    }
+}
@@ -0,0 +1,145 @@
+//! Gamma-corrected brightness ramps, and content changes hidden behind a fade out and in
+
+use crate::hal::blocking::delay::DelayMs;
+use crate::hal::blocking::i2c::{Write, WriteRead};
+use crate::{Error, TWIDisplay};
+
+/// Brightness ramp between two values over a given time.
+///
+/// The ramp is linear in perceived brightness (gamma 2 curve), so that it looks even
+/// instead of jumping at low levels and crawling at high levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fade {
+    from: u8,
+    to: u8,
+    duration_ms: u32,
+    elapsed_ms: u32,
+}
+
+impl Fade {
+    /// Create a new fade from one brightness to another, lasting `duration_ms` milliseconds
+    pub fn new(from: u8, to: u8, duration_ms: u32) -> Self {
+        Fade {
+            from,
+            to,
+            duration_ms,
+            elapsed_ms: 0,
+        }
+    }
+
+    /// Check if the target brightness has been reached
+    pub fn is_done(&self) -> bool {
+        self.elapsed_ms >= self.duration_ms
+    }
+
+    /// Brightness at the current point of the fade
+    pub fn brightness(&self) -> u8 {
+        if self.is_done() {
+            return self.to;
+        }
+        if self.elapsed_ms == 0 {
+            return self.from;
+        }
+
+        let from = perceived(self.from) as i64;
+        let to = perceived(self.to) as i64;
+        let level = from + (to - from) * self.elapsed_ms as i64 / self.duration_ms as i64;
+
+        actual(level as u8)
+    }
+
+    /// Move the fade forward by `elapsed_ms` milliseconds, return the new brightness
+    pub fn advance(&mut self, elapsed_ms: u32) -> u8 {
+        self.elapsed_ms = self
+            .elapsed_ms
+            .saturating_add(elapsed_ms)
+            .min(self.duration_ms);
+        self.brightness()
+    }
+
+    /// Move the fade forward and set the display brightness.
+    /// Returns `true` while the fade is still running.
+    pub fn update<I2C, D, E>(
+        &mut self,
+        display: &mut TWIDisplay<I2C, D>,
+        elapsed_ms: u32,
+    ) -> Result<bool, Error<E>>
+    where
+        I2C: Write<Error = E> + WriteRead<Error = E>,
+        D: DelayMs<u16>,
+    {
+        let brightness = self.advance(elapsed_ms);
+        display.set_brightness(brightness)?;
+        Ok(!self.is_done())
+    }
+}
+
+/// Content change hidden by fading out to dark and back in to the previous brightness
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FadeTransition {
+    fade_out: Fade,
+    fade_in: Fade,
+    changed: bool,
+}
+
+impl FadeTransition {
+    /// Create a new transition at the given brightness, each fade lasting `duration_ms` milliseconds
+    pub fn new(brightness: u8, duration_ms: u32) -> Self {
+        FadeTransition {
+            fade_out: Fade::new(brightness, 0, duration_ms),
+            fade_in: Fade::new(0, brightness, duration_ms),
+            changed: false,
+        }
+    }
+
+    /// Check if the transition is complete
+    pub fn is_done(&self) -> bool {
+        self.changed && self.fade_in.is_done()
+    }
+
+    /// Move the transition forward by `elapsed_ms` milliseconds.
+    ///
+    /// `change` is called once, when the display is dark. Returns `true` while the transition is still running.
+    pub fn update<I2C, D, E, F>(
+        &mut self,
+        display: &mut TWIDisplay<I2C, D>,
+        elapsed_ms: u32,
+        change: F,
+    ) -> Result<bool, Error<E>>
+    where
+        I2C: Write<Error = E> + WriteRead<Error = E>,
+        D: DelayMs<u16>,
+        F: FnOnce(&mut TWIDisplay<I2C, D>) -> Result<(), Error<E>>,
+    {
+        if !self.changed {
+            // also with a zero duration, the display is set dark before the change
+            if self.fade_out.update(display, elapsed_ms)? {
+                return Ok(true);
+            }
+            change(display)?;
+            self.changed = true;
+            return Ok(true);
+        }
+
+        self.fade_in.update(display, elapsed_ms)
+    }
+}
+
+/// Convert a brightness to the perceived level (inverse of the gamma 2 curve)
+fn perceived(brightness: u8) -> u8 {
+    isqrt(brightness as u32 * 255) as u8
+}
+
+/// Convert a perceived level to brightness (gamma 2 curve)
+fn actual(level: u8) -> u8 {
+    (level as u32 * level as u32 / 255) as u8
+}
+
+/// Integer square root
+fn isqrt(value: u32) -> u32 {
+    let mut root = 0;
+    while (root + 1) * (root + 1) <= value {
+        root += 1;
+    }
+    root
+}
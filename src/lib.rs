@@ -6,9 +6,13 @@
 //! - Display text, although some characters may not be available (see display documentation)
 //! - Clear the display
 //! - Show the current I2C address
-//! - Change the I2C address (experimental function)
+//! - Find all the displays connected to the bus
+//! - Use several displays side by side as one wide display
+//! - Share the bus with other devices, by borrowing it or through bus sharing proxies
+//! - Change the I2C address (experimental function), with verification and recovery through the general call address
 //! - Display time in HH.MM format
 //! - Display temperature or humidity, with settable lower/upper threshold
+//! - Light custom segment patterns, bar graphs and animations (experimental function)
 //!
 //!## The device
 //! The TWI 7-segment Display is an easy to use 4-digit 7-segment display that is controlled using the TWI (I2C compatible) protocol.
@@ -25,8 +29,9 @@
 //! ### Initialization
 //! A new instance of the device is created as follows:
 //!
-//! ```rust
-//! use akafugu_twidisplay::*:
+//! ```no_run
+//! # let mut i2c = embedded_hal_mock::I2cMock::new();
+//! use akafugu_twidisplay::*;
 //!
 //! let mut akafugu = TWIDisplay::new(i2c, DEFAULT_ADDRESS);
 //! ```
@@ -34,17 +39,91 @@
 //! The default address is 0x12. If the address was changed with the `set_address()` function,
 //! the new address must be used after a power down-power up sequence.  
 //!
+//! The microcontroller of the display needs a moment after power up. Instead of a fixed delay,
+//! the driver can wait until the display acknowledges its address, with a timeout in milliseconds.
+//! It polls the firmware revision register (a one byte `write_read` of 0x8A), ignoring the value read:
+//!
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut delay = embedded_hal_mock::DelayMockNoop;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! akafugu.wait_ready(&mut delay, 500).unwrap();
+//! ```
+//!
+//! The usual setup after boot can be done in one go with the builder. `init()` clears the display,
+//! then applies the mode, the brightness and the startup text:
+//!
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut i2c = embedded_hal_mock::I2cMock::new();
+//! # let mut delay = embedded_hal_mock::DelayMockNoop;
+//! let mut akafugu = TWIDisplayBuilder::new()
+//!     .address(DEFAULT_ADDRESS)
+//!     .mode(Mode::Scroll)
+//!     .brightness(127)
+//!     .digits(4) // optional check of the number of digits reported by the display
+//!     .text("HI")
+//!     .init(i2c)
+//!     .unwrap();
+//!
+//! // or, waiting for the display to be ready first
+//! # let i2c = embedded_hal_mock::I2cMock::new();
+//! let mut akafugu = TWIDisplayBuilder::new()
+//!     .brightness(127)
+//!     .init_when_ready(i2c, &mut delay, 500)
+//!     .unwrap();
+//! ```
+//!
+//! ### Sharing the bus with other devices
+//!
+//! The driver takes ownership of the bus. To use the same bus for other devices (sensors, RTC),
+//! either borrow it with `I2cRef`, or give the driver a proxy from a bus sharing crate
+//! such as [`shared-bus`](https://crates.io/crates/shared-bus):
+//!
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # use embedded_hal::blocking::i2c::WriteRead;
+//! # mod shared_bus {
+//! #     pub struct BusManagerSimple<B>(B);
+//! #     impl<B> BusManagerSimple<B> {
+//! #         pub fn new(bus: B) -> Self { BusManagerSimple(bus) }
+//! #         pub fn acquire_i2c(&self) -> embedded_hal_mock::I2cMock<'static> { embedded_hal_mock::I2cMock::new() }
+//! #     }
+//! # }
+//! # let mut i2c = embedded_hal_mock::I2cMock::new();
+//! # struct SomeRtc<B>(B);
+//! # impl<B> SomeRtc<B> { fn new(bus: B) -> Self { SomeRtc(bus) } }
+//! # const RTC_ADDRESS: u8 = 0x68;
+//! # let mut buffer = [0u8; 1];
+//! // borrowed bus: the driver must be destroyed before using the bus for something else
+//! let mut akafugu = TWIDisplay::new(I2cRef::new(&mut i2c), DEFAULT_ADDRESS);
+//! akafugu.display_number(1234).unwrap();
+//! akafugu.destroy();
+//! i2c.write_read(RTC_ADDRESS, &[0x00], &mut buffer).unwrap();
+//!
+//! // shared bus: each driver gets its own proxy
+//! let bus = shared_bus::BusManagerSimple::new(i2c);
+//! let mut akafugu = TWIDisplay::new(bus.acquire_i2c(), DEFAULT_ADDRESS);
+//! let mut rtc = SomeRtc::new(bus.acquire_i2c());
+//! ```
+//!
+//! Any proxy implementing the `embedded-hal` 0.2 I2C traits can be used the same way.
+//!
 //!
 //! ### Main functions
 //!
 //! Display can be cleared with the following command:
-//! ```rust
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
 //! akafugu.clear_display().unwrap();
 //! ```
 //!
 //! Digits and/or characters can either be simply sent to display, or displayed at defined positions.
 //!
-//! ```rust
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
 //! // display digit '7' at position 2 (positions are 0,1,2,3 from left to right)
 //! akafugu.display_digit(2, 7).unwrap();
 //! // display character 'P' at position 3
@@ -54,7 +133,9 @@
 //! If a digit/character is just sent to the display, it will appear according to the selected mode
 //! (scroll or rotate) - please see the documentation.
 //!
-//! ```rust
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
 //! akafugu.send_char('A').unwrap();
 //! akafugu.send_char('B').unwrap();
 //! akafugu.send_char('C').unwrap();
@@ -63,7 +144,9 @@
 //!
 //! This will display `ABCD`.
 //!
-//! ```rust
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
 //! akafugu.send_char('E').unwrap();
 //! ```
 //!
@@ -73,54 +156,388 @@
 //!
 //! Text can be sent to display as string literals:
 //!
-//! ```rust
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
 //! akafugu.send_text("HELLO LOOP PULL CALL").unwrap();
 //! ```
 //!
 //! Numbers from 0-9999 range can be displayed with the following function:
-//! ```rust
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
 //! akafugu.display_number(1234).unwrap();
 //! ```
 //! _NOTE_: Numbers will be displayed with leading zeroes, e.g. `0023`.
 //!
+//! Any combination of segments can be lit at a position:
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! // top and bottom segments at position 0
+//! akafugu.display_segments(0, Segment::A | Segment::D).unwrap();
+//! ```
+//!
+//! __NOTE:__ Segment patterns are experimental. They are sent as `[0x84, position, segments]`,
+//! assuming the custom character register of the display and the bit order of the `Segment`
+//! constants; neither has been verified on hardware yet. The same applies to everything built on them:
+//! `display_bar()`, animations with segment patterns (including the built-in ones) and the segment walk of the self-test.
+//!
+//! Fill levels can be shown as a horizontal bar with 8 steps, or as a percentage:
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! // bar filled to 3/8
+//! akafugu.display_bar(37, 100).unwrap();
+//! // ` 37P`
+//! akafugu.display_percentage(37, 100).unwrap();
+//! ```
+//!
+//! During long operations an animation can show that something is going on. Animations don't block:
+//! they are moved forward on every tick. Built-in ones are `SPINNER`, `WALKING_DASH` and `PERIMETER`,
+//! custom ones are lists of frames made of characters and segment patterns:
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # use embedded_hal::blocking::delay::DelayMs;
+//! # let mut delay = embedded_hal_mock::DelayMockNoop;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! # fn busy() -> bool { false }
+//! let mut spinner = Animation::new(&SPINNER, 100);
+//! while busy() {
+//!     spinner.update(&mut akafugu, 10).unwrap();
+//!     delay.delay_ms(10_u32);
+//! }
+//!
+//! const LOADING: [Frame; 2] = [
+//!     [Glyph::Char('L'), Glyph::Char('O'), Glyph::Char('A'), Glyph::Char('D')],
+//!     [Glyph::Segments(Segment::G); 4],
+//! ];
+//! let mut loading = Animation::new(&LOADING, 500);
+//! ```
+//!
+//! Dashboards can rotate pages, each shown for its own time. A page is a closure (or any type
+//! implementing `Page`) that renders to the display. The pager also holds alert pages,
+//! which preempt the rotation whenever they are raised:
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # use embedded_hal::blocking::delay::DelayMs;
+//! # let mut delay = embedded_hal_mock::DelayMockNoop;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! # struct Rtc;
+//! # impl Rtc { fn hours(&self) -> u8 { 12 } fn minutes(&self) -> u8 { 30 } }
+//! # struct Sensor;
+//! # impl Sensor { fn celsius(&self) -> i16 { 21 } }
+//! # let (rtc, sensor) = (Rtc, Sensor);
+//! # fn door_opened() -> bool { false }
+//! let mut time = |d: &mut TWIDisplay<_, _>| d.display_time(rtc.hours(), rtc.minutes(), true);
+//! let mut temperature = |d: &mut TWIDisplay<_, _>| d.display_temperature(sensor.celsius(), TempUnits::Celsius, None, None);
+//! let mut open = |d: &mut TWIDisplay<_, _>| d.send_text("OPEN");
+//! let mut pager = Pager::new(
+//!     [(&mut time as &mut dyn Page<_, _, _>, 5000), (&mut temperature, 3000)],
+//!     [&mut open],
+//! );
+//!
+//! loop {
+//!     if door_opened() {
+//!         // alert 0 for 2 seconds, with priority 1
+//!         pager.interrupt(0, 1, Some(2000)).unwrap();
+//!     }
+//!     pager.update(&mut akafugu, 100).unwrap();
+//!     delay.delay_ms(100_u32);
+//! }
+//! ```
+//!
+//! Temporary messages, e.g. alerts, can be shown over the normal content, which is restored afterwards.
+//! The queue has a fixed capacity; messages with a higher priority are shown first:
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # use embedded_hal::blocking::delay::DelayMs;
+//! # let mut delay = embedded_hal_mock::DelayMockNoop;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! # let (hours, minutes) = (12, 30);
+//! let mut messages: MessageQueue<4> = MessageQueue::new();
+//! messages.push(Message::new("OPEN", 1, 2000)).ok();
+//! messages.push(Message::new("Err3", 5, 3000)).ok();
+//! loop {
+//!     if !messages.is_showing() {
+//!         akafugu.display_time(hours, minutes, true).unwrap();
+//!     }
+//!     messages.update(&mut akafugu, 100).unwrap();
+//!     delay.delay_ms(100_u32);
+//! }
+//! ```
+//!
+//! Two values from 0 to 99, e.g. a score, can be shown side by side with a dot between them.
+//! Padding of each side and the overflow marker can be set with `PairFormat`:
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! akafugu.display_pair(12, 7, true).unwrap(); // "12.07"
+//! let format = PairFormat::new(Padding::Blank, Padding::Blank, 'E');
+//! akafugu.display_pair_with_format(3, 120, false, format).unwrap(); // " 3EE"
+//! ```
+//!
+//! Error codes and device status use the same conventions across devices: every position is written
+//! and the dots are turned off, so nothing of the previous content is left:
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! akafugu.display_error(42).unwrap(); // "E 42"
+//! akafugu.display_status(Status::Off).unwrap(); // "OFF "
+//! ```
+//!
+//! For production test, a self-test lights every segment, walks each segment and dot,
+//! sweeps the brightness and sends text in both modes. A test jig confirms every step:
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut delay = embedded_hal_mock::DelayMockNoop;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! # struct Camera;
+//! # impl Camera { fn check(&mut self, _: TestStep) -> bool { true } }
+//! # let mut camera = Camera;
+//! let failed = SelfTest::new()
+//!     .dwell(300)
+//!     .run(&mut akafugu, &mut delay, &mut |step: TestStep| camera.check(step))
+//!     .unwrap();
+//! if let Some(step) = failed {
+//!     // report the failed step
+//! }
+//! ```
+//!
 //! Dots can be turned on or off using this function:
-//! ```rust
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
 //! // this will turn on the first and the third dot from the left
 //! akafugu.display_dots([true, false, true, false]).unwrap();
 //! ```
 //!
 //!
+//! ### Several displays side by side
+//!
+//! Displays mounted next to each other can be used as one wide display.
+//! Each of them needs its own address:
+//!
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # use embedded_hal::blocking::delay::DelayMs;
+//! # let (i2c_proxy_1, i2c_proxy_2) = (embedded_hal_mock::I2cMock::new(), embedded_hal_mock::I2cMock::new());
+//! # let mut delay = embedded_hal_mock::DelayMockNoop;
+//! let left = TWIDisplay::new(i2c_proxy_1, 0x12);
+//! let right = TWIDisplay::new(i2c_proxy_2, 0x13);
+//!
+//! let mut chain = DisplayChain::new([left, right]);
+//!
+//! chain.display_number(12345678).unwrap();
+//! chain.display_text("HELLO").unwrap();
+//! chain.display_dots(&[false, false, false, true, false, false, false, false]).unwrap();
+//!
+//! // scroll text through both displays, one step per tick
+//! for offset in 0..20 {
+//!     chain.scroll_text("HELLO LOOP", offset).unwrap();
+//!     delay.delay_ms(300_u32);
+//! }
+//! ```
+//!
+//! Positions and dots can blink, e.g. to show which field is being edited in a settings menu.
+//! The blink doesn't block: it is moved forward on every tick, and the content underneath is kept:
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # use embedded_hal::blocking::delay::DelayMs;
+//! # let mut delay = embedded_hal_mock::DelayMockNoop;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! # fn next_field_pressed() -> bool { false }
+//! akafugu.display_time(12, 30, true).unwrap();
+//!
+//! // blink the hours, twice per second
+//! let mut blink = Blink::new([true, true, false, false], [false; 4], 500);
+//!
+//! loop {
+//!     blink.update(&mut akafugu, 10).unwrap();
+//!     if next_field_pressed() {
+//!         // move to the minutes
+//!         blink.select(&mut akafugu, [false, false, true, true], [false; 4]).unwrap();
+//!     }
+//!     delay.delay_ms(10_u32);
+//! }
+//! ```
+//!
 //! ### Control functions
 //!
 //! Display mode can be changed as follows:
 //!
-//! ```rust
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
 //! akafugu.set_mode(Mode::Scroll).unwrap(); // default mode is `Rotate`
 //! ```
 //!
+//! The driver remembers the mode it set, so the result of `send_text` is predictable.
+//! The mode can also be switched only for a while:
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! assert_eq!(akafugu.mode(), Some(Mode::Scroll));
+//! akafugu.send_text_with_mode("ABCD", Mode::Rotate).unwrap();
+//! akafugu.with_mode(Mode::Rotate, |d| d.send_char('E')).unwrap();
+//! assert_eq!(akafugu.mode(), Some(Mode::Scroll));
+//! ```
+//!
 //! Brightness can be set between 0 and 255, where 127 is approx. 50% brightness.
-//! ```rust
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
 //! akafugu.set_brightness(200).unwrap();
 //! ```
 //!
+//! Brightness changes can be faded instead. The fade doesn't block: it is moved forward
+//! on every tick of the application, by the time elapsed since the previous tick:
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # use embedded_hal::blocking::delay::DelayMs;
+//! # let mut delay = embedded_hal_mock::DelayMockNoop;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! // fade from 0 to 255 in one second
+//! let mut fade = Fade::new(0, 255, 1000);
+//! while fade.update(&mut akafugu, 20).unwrap() {
+//!     delay.delay_ms(20_u32);
+//! }
+//! ```
+//!
+//! Content changes can be hidden by fading out, changing the content, and fading back in:
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # use embedded_hal::blocking::delay::DelayMs;
+//! # let mut delay = embedded_hal_mock::DelayMockNoop;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! let mut transition = FadeTransition::new(akafugu.brightness().unwrap_or(255), 300);
+//! while transition.update(&mut akafugu, 20, |d| d.display_number(42)).unwrap() {
+//!     delay.delay_ms(20_u32);
+//! }
+//! ```
+//!
+//! Brightness can also follow the ambient light. Any sensor can be used by implementing `LightSensor`;
+//! the curve maps illuminance in lux to brightness, and small or sudden changes are smoothed out:
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # use embedded_hal::blocking::delay::DelayMs;
+//! # let mut delay = embedded_hal_mock::DelayMockNoop;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! # struct Sensor;
+//! # impl LightSensor for Sensor { fn lux(&mut self) -> Option<u32> { None } }
+//! # let mut light_sensor = Sensor;
+//! // dark at night, full brightness in daylight
+//! const CURVE: [(u32, u8); 3] = [(0, 5), (100, 80), (1000, 255)];
+//!
+//! let mut auto = AutoBrightness::new(&CURVE).hysteresis(10).max_step(8);
+//!
+//! loop {
+//!     auto.update_from(&mut akafugu, &mut light_sensor).unwrap();
+//!     delay.delay_ms(200_u32);
+//! }
+//! ```
+//!
 //! The I2C address of the device can be changed from the default 0x12 as follows:
-//! ```rust
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
 //! akafugu.set_address(0x20).unwrap();
 //! ```
 //!
 //! The new address will be active after a power down, power up sequence.
 //!
+//! A safer way of changing the address lets the driver follow the change and check the result:
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! akafugu.begin_address_change(0x20).unwrap();
+//!
+//! // power cycle the display, then:
+//! akafugu.complete_address_change().unwrap(); // driver now talks to 0x20
+//! ```
+//!
+//! If the device does not respond anymore, a new address can be sent through the general call address 0x00:
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! akafugu.recover_address(DEFAULT_ADDRESS).unwrap();
+//! // power cycle the display, then:
+//! akafugu.complete_address_change().unwrap();
+//! ```
+//!
 //! __NOTE:__ According to the documentation, the allowed range of addresses is 0x00-0x7F,
 //! but addresses including and over 0x40 don't seem to work correctly, even though
 //! they are correctly displayed. In such case 0x00 must be used to access the device and change the address again.
-//! For this reason in this driver the address setting is restricted to 0x00-0x39 range.
+//! For this reason in this driver the address setting is restricted to 0x08-0x3F range
+//! (0x00-0x07 are reserved by the I2C specification), and other values are rejected with `Error::AddressOutOfRange`.
+//!
+//! Several displays on one bus can be found with `scan()`, which reads the firmware revision
+//! at every non-reserved address and keeps the ones answering with a plausible value.
+//! Other devices acknowledging their address receive the register byte 0x8A as well:
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut i2c = embedded_hal_mock::I2cMock::new();
+//! let mut found = [0u8; 8];
+//! let count = scan(&mut i2c, &mut found);
+//! for addr in &found[..count] {
+//!     // a TWIDisplay answers at `addr`
+//! }
+//! ```
 //!
 //! To show the current I2C address use the following command:
-//! ```rust
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
 //! akafugu.display_address().unwrap();
 //! ```
 //! The same can be achieved by simply connecting only the VCC and GND pins of the display.
 //!
+//! ### Retrying failed writes
+//!
+//! The display can occasionally refuse a command while it is busy. Failed writes can be retried,
+//! waiting between attempts with a delay provider; the number of retries and failures is counted:
+//!
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut i2c = embedded_hal_mock::I2cMock::new();
+//! # let mut delay = embedded_hal_mock::DelayMockNoop;
+//! // up to 3 attempts, waiting 2 ms before the first retry and 4 ms before the second
+//! let mut akafugu = TWIDisplay::new(i2c, DEFAULT_ADDRESS).with_retry(RetryPolicy::new(3, 2), delay);
+//!
+//! akafugu.display_number(1234).unwrap();
+//!
+//! let stats = akafugu.retry_stats();
+//! // stats.retries, stats.failures
+//! ```
+//!
+//! ### Bus statistics
+//!
+//! The driver counts the transactions, the bytes sent and received, and the failed writes and reads,
+//! remembering the last failure. A display that stays blank while no errors are counted points
+//! to the display itself rather than to the wiring:
+//!
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! let stats = akafugu.stats();
+//! if let Some(error) = stats.last_error {
+//!     // error.kind, error.command, error.transaction
+//! }
+//! akafugu.reset_stats();
+//! ```
+//!
+//! ### Redundant writes
+//!
+//! The driver remembers the last brightness, mode, dots and characters sent, and skips the commands
+//! that would not change anything (e.g. `display_time()` called every second only updates the changed digits).
+//! If the display lost power, its state is unknown: call `invalidate()` so that everything is sent again.
+//!
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! akafugu.invalidate();
+//! ```
+//!
 //! ### Convenience functions
 //! The driver has three additional functions, that can be useful for clock or sensor applications.
 //!
@@ -128,7 +545,10 @@
 //!
 //! Time is displayed in HH.MM format, with the central dot displayed or not:
 //!
-//! ```rust
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! # fn some_rtc_function() -> (u8, u8, u8) { (12, 30, 0) }
 //!
 //! // get time from the clock
 //! let (hours, minutes, seconds) = some_rtc_function();
@@ -146,7 +566,10 @@
 //!
 //! Date can be displayed either in MMDD or DDMM format, with the central dot on or off.
 //!
-//! ```rust
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! # fn some_rtc_function() -> (u8, u8) { (10, 17) }
 //!
 //! // get date from the clock
 //! let (month, day) = some_rtc_function();
@@ -167,12 +590,27 @@
 //! Thresholds are optional and if not given, will default to the minimum and maximum limits, which are set to -99 and 999, respectively.
 //! If the supplied value exceeds the limit, the display will show `----`.  
 //!
-//! ```rust
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! # fn some_sensor_reading() -> i16 { 21 }
 //! let temp_reading = some_sensor_reading();
 //! // display temperature with unit 'C', lower threshold at -50 degrees,
 //! // no upper threshold (defaults to +999)
 //! // temp_reading < -50 will show as `-LL-`, temp_reading < -99 will show as `----`
-//! akafugu.display_temperature(temperature, TempUnits::Celsius, Some(-50), None).unwrap();
+//! akafugu.display_temperature(temp_reading, TempUnits::Celsius, Some(-50), None).unwrap();
+//! ```
+//!
+//! Temperature can also be displayed with one decimal place, e.g. `23.5C`, given in tenths of a degree
+//! or as `f32`. The reading is converted if the display unit is different:
+//!
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! // reading of 23.5 Celsius degrees, shown as `23.5C`
+//! akafugu.display_temperature_decimal(235, TempUnits::Celsius, TempUnits::Celsius).unwrap();
+//! // the same reading shown as `74.3F`
+//! akafugu.display_temperature_f32(23.5, TempUnits::Celsius, TempUnits::Fahrenheit).unwrap();
 //! ```
 //!
 //! #### Display humidity
@@ -185,12 +623,77 @@
 //! Thresholds are optional and if not given, will default to the minimum and maximum limits, which are set to 0 and 100, respectively.
 //! If the supplied value exceeds the limit, the display will show `----`.  
 //!
-//! ```rust
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! # fn some_sensor_reading() -> i16 { 21 }
 //! let hum_reading = some_sensor_reading();
 //! // display humidity, lower threshold at 10%, upper threshold at 90%.
 //! // temp_reading < 10 will show as `-LL-`, temp_reading > 90 will show as `-HH-`,
 //! // readings below 0 or above 100 will show as `----`
-//! akafugu.display_humidity(humidity, Some(10), Some(90)).unwrap();
+//! akafugu.display_humidity(hum_reading, Some(10), Some(90)).unwrap();
+//! ```
+//!
+//! #### Display other measurements
+//!
+//! Any measurement can be displayed with a unit deciding its suffix, range and default thresholds:
+//! pressure and CO2 use all four digits, the other units three digits followed by a suffix.
+//!
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! let mut policy = ThresholdPolicy::new();
+//!
+//! // `1013`, values below 300 or above 1100 show as `-LL-`/`-HH-`
+//! akafugu.display_measurement(1013, Unit::Pressure, &mut policy).unwrap();
+//! // ` 12U`
+//! akafugu.display_measurement(12, Unit::Voltage, &mut policy).unwrap();
+//! // custom suffix: ` 42d`
+//! akafugu.display_measurement(42, Unit::Custom('d'), &mut policy).unwrap();
+//! ```
+//!
+//! #### Display large or small values
+//!
+//! Values that don't fit in four digits can be scaled with an SI prefix, shown at the last position,
+//! with a configurable number of decimal places and overflow marker:
+//!
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! # let value = 1.0e9_f32;
+//! // `12.3k`
+//! akafugu.display_si(12345.0, SiFormat::default()).unwrap();
+//! // `1.2M`
+//! akafugu.display_si(1_200_000.0, SiFormat::default()).unwrap();
+//! // `999m`
+//! akafugu.display_si(0.999, SiFormat::default()).unwrap();
+//! // no decimal places, `OFL ` if too large
+//! akafugu.display_si(value, SiFormat::new(0, ['O', 'F', 'L', ' '])).unwrap();
+//! ```
+//!
+//! #### Thresholds with hysteresis
+//!
+//! Readings close to a threshold can jump between the value and `-LL-`/`-HH-`. A `ThresholdPolicy`
+//! adds hysteresis: once a threshold is crossed, the reading must come back by the hysteresis
+//! before the value is shown again. It can also blink the label, turn on an alarm dot
+//! and use custom labels. The policy remembers the last reading, so keep one instance per sensor:
+//!
+//! ```no_run
+//! # use akafugu_twidisplay::*;
+//! # let mut akafugu = TWIDisplay::new(embedded_hal_mock::I2cMock::new(), DEFAULT_ADDRESS);
+//! # fn some_sensor_reading() -> i16 { 21 }
+//! let mut policy = ThresholdPolicy::new()
+//!     .thresholds(Some(-30), Some(60))
+//!     .hysteresis(2)
+//!     .labels(['L', 'O', ' ', ' '], ['H', 'I', ' ', ' '])
+//!     .blink(true)
+//!     .alarm_dot(Some(3));
+//!
+//! loop {
+//!     let temp_reading = some_sensor_reading();
+//!     // above 60 shows `HI`, blinking, with the last dot on, until the reading drops to 58
+//!     akafugu.display_temperature_with_policy(temp_reading, TempUnits::Celsius, &mut policy).unwrap();
+//! }
 //! ```
 
 // TO DO:
@@ -222,8 +725,6 @@ FUNCTIONS:
 
 USE '-' FOR DIGITS IF INCORRECT (DOES IT MAKE SENSE?)
 
-OTHER:
-- CUSTOM CHARACTERS
 
 */
 
@@ -233,8 +734,62 @@ OTHER:
 
 use embedded_hal as hal;
 
+use core::fmt;
+
+use hal::blocking::delay::DelayMs;
 use hal::blocking::i2c::{Write, WriteRead};
 
+mod animation;
+pub use animation::{Animation, Frame, Glyph, PERIMETER, SPINNER, WALKING_DASH};
+
+mod auto_brightness;
+pub use auto_brightness::{AutoBrightness, LightSensor};
+
+mod blink;
+pub use blink::Blink;
+
+mod bus;
+pub use bus::I2cRef;
+
+mod builder;
+pub use builder::TWIDisplayBuilder;
+
+mod chain;
+pub use chain::DisplayChain;
+
+mod fade;
+pub use fade::{Fade, FadeTransition};
+
+mod message;
+pub use message::{Message, MessageQueue};
+
+mod pager;
+pub use pager::{Page, Pager};
+
+mod pair;
+pub use pair::{Padding, PairFormat};
+
+mod retry;
+pub use retry::{NoDelay, RetryPolicy, RetryStats};
+
+mod self_test;
+pub use self_test::{SelfTest, TestJig, TestStep};
+
+mod si;
+pub use si::SiFormat;
+
+mod status;
+pub use status::Status;
+
+mod stats;
+pub use stats::{LastError, Stats, TransactionKind};
+
+mod threshold;
+pub use threshold::{ThresholdPolicy, ThresholdState};
+
+mod unit;
+pub use unit::Unit;
+
 /// All possible errors in this crate
 #[derive(Debug)]
 pub enum Error<E> {
@@ -242,6 +797,42 @@ pub enum Error<E> {
     I2C(E),
     /// Invalid input data
     InvalidInputData,
+    /// Position outside of the 0-3 range
+    InvalidPosition,
+    /// Digit outside of the 0-9 range
+    InvalidDigit,
+    /// Character that cannot be sent to the display (non-ASCII)
+    UnsupportedChar,
+    /// I2C address outside of the supported 0x08-0x3F range
+    AddressOutOfRange,
+    /// Value that cannot be displayed (e.g. number over 9999, invalid time or date)
+    ValueOutOfRange,
+    /// No address change has been started with `begin_address_change()` or `recover_address()`
+    NoAddressChangePending,
+    /// The display reported a different number of digits than expected
+    UnexpectedDigitCount(u8),
+    /// The display did not answer within the given time
+    Timeout,
+    /// The mode of the display is not known, so it cannot be set back (see `with_mode()`)
+    UnknownMode,
+}
+
+impl<E: fmt::Debug> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::I2C(e) => write!(f, "I2C bus error: {:?}", e),
+            Error::InvalidInputData => f.write_str("invalid input data"),
+            Error::InvalidPosition => f.write_str("position must be between 0 and 3"),
+            Error::InvalidDigit => f.write_str("digit must be between 0 and 9"),
+            Error::UnsupportedChar => f.write_str("character not supported by the display"),
+            Error::AddressOutOfRange => f.write_str("I2C address must be between 0x08 and 0x3F"),
+            Error::ValueOutOfRange => f.write_str("value out of displayable range"),
+            Error::NoAddressChangePending => f.write_str("no address change pending"),
+            Error::UnexpectedDigitCount(n) => write!(f, "display reported {} digits", n),
+            Error::Timeout => f.write_str("display did not answer in time"),
+            Error::UnknownMode => f.write_str("mode of the display unknown"),
+        }
+    }
 }
 
 struct Register;
@@ -252,19 +843,65 @@ impl Register {
     const I2C_ADDRESS_SETTING: u8 = 0x81;
     const CLEAR_DISPLAY: u8 = 0x82;
     const MODE_SETTING: u8 = 0x83;
-    const _CUSTOM_CHAR: u8 = 0x84; // not implemented yet
+    const CUSTOM_CHAR: u8 = 0x84;
     const DOTS: u8 = 0x85;
     //const _DISPLAY_TIME          :u8 = 0x87; // not sure if this works
     //const _DISPLAY_WORD          :u8 = 0x88;
     const POSITION_SETTING: u8 = 0x89;
-    const _FIRMWARE_REV: u8 = 0x8a;
-    const _NUMBER_DIGITS: u8 = 0x8b;
+    const FIRMWARE_REV: u8 = 0x8a;
+    const NUMBER_DIGITS: u8 = 0x8b;
     const DISPLAY_ADDRESS: u8 = 0x90;
 }
 
 /// Default I2C address for the device
 pub const DEFAULT_ADDRESS: u8 = 0x12;
 
+/// General call address, the device always answers on it (used for recovery)
+pub const GENERAL_CALL_ADDRESS: u8 = 0x00;
+
+/// Time between two attempts of `wait_ready()`, in milliseconds
+const READY_POLL_MS: u16 = 5;
+
+/// Lowest non-reserved 7-bit I2C address, probed by `scan()` and accepted by `set_address()`
+pub const SCAN_FIRST_ADDRESS: u8 = 0x08;
+
+/// Highest address accepted by `set_address()`, higher ones don't seem to work correctly
+const LAST_SETTABLE_ADDRESS: u8 = 0x3F;
+
+/// Highest non-reserved 7-bit I2C address, probed by `scan()`
+pub const SCAN_LAST_ADDRESS: u8 = 0x77;
+
+/// Scan the bus for TWIDisplays, writing the addresses found into `found`.
+///
+/// The firmware revision is read at every non-reserved 7-bit address (0x08-0x77), so
+/// __every device acknowledging its address receives the register byte 0x8A__, including other devices
+/// on the bus. A device counts as a TWIDisplay if it answers with a revision other than 0x00 or 0xFF
+/// (reading has not been verified on hardware yet).
+/// Returns the number of addresses found; scanning stops when `found` is full.
+pub fn scan<I2C, E>(i2c: &mut I2C, found: &mut [u8]) -> usize
+where
+    I2C: WriteRead<Error = E>,
+{
+    let mut count = 0;
+
+    for addr in SCAN_FIRST_ADDRESS..=SCAN_LAST_ADDRESS {
+        if count == found.len() {
+            break;
+        }
+
+        let mut rev = [0];
+        match i2c.write_read(addr, &[Register::FIRMWARE_REV], &mut rev) {
+            Ok(()) if rev[0] != 0x00 && rev[0] != 0xFF => {
+                found[count] = addr;
+                count += 1;
+            }
+            _ => (),
+        }
+    }
+
+    count
+}
+
 /// Possible choices for temperature units
 #[allow(non_camel_case_types)]
 #[derive(Copy, Clone, Debug)]
@@ -275,6 +912,45 @@ pub enum TempUnits {
     Fahrenheit,
 }
 
+impl TempUnits {
+    /// Character displayed after the value
+    pub fn symbol(self) -> char {
+        match self {
+            TempUnits::Celsius => 'C',
+            TempUnits::Fahrenheit => 'F',
+        }
+    }
+
+    /// Convert a temperature in tenths of a degree from this unit to another, rounding to the nearest tenth
+    pub fn convert_tenths(self, tenths: i16, to: TempUnits) -> i16 {
+        let value = tenths as i32;
+        let converted = match (self, to) {
+            (TempUnits::Celsius, TempUnits::Fahrenheit) => div_round(value * 9, 5) + 320,
+            (TempUnits::Fahrenheit, TempUnits::Celsius) => div_round((value - 320) * 5, 9),
+            _ => value,
+        };
+        converted.clamp(i16::MIN as i32, i16::MAX as i32) as i16
+    }
+
+    /// Convert a temperature from this unit to another
+    pub fn convert(self, value: f32, to: TempUnits) -> f32 {
+        match (self, to) {
+            (TempUnits::Celsius, TempUnits::Fahrenheit) => value * 9.0 / 5.0 + 32.0,
+            (TempUnits::Fahrenheit, TempUnits::Celsius) => (value - 32.0) * 5.0 / 9.0,
+            _ => value,
+        }
+    }
+}
+
+/// Integer division rounding half away from zero
+fn div_round(numerator: i32, denominator: i32) -> i32 {
+    if (numerator < 0) == (denominator < 0) {
+        (numerator + denominator / 2) / denominator
+    } else {
+        (numerator - denominator / 2) / denominator
+    }
+}
+
 /// Possible choices for date format
 #[allow(non_camel_case_types)]
 #[derive(Copy, Clone, Debug)]
@@ -286,7 +962,7 @@ pub enum DateFormat {
 }
 
 #[allow(non_camel_case_types)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 /// Two possible display modes
 pub enum Mode {
     /// Scroll
@@ -295,21 +971,149 @@ pub enum Mode {
     Rotate,
 }
 
-/// TWIDisplay driver, that holds the I2C bus instance and the I2C address used
+// dots are numbered 1,2,3,4 from the left, and they correspond to bits
+// so 0b0000_0010 is bit 1, dot 1, 0b0000_1000 is bit 3, dot 3 and so on
+
+/// Value of the dots register for the given switches
+pub(crate) fn dot_mask(dots: [bool; 4]) -> u8 {
+    let mut dotvalues: u8 = 0;
+
+    for (idx, dot) in dots.iter().enumerate() {
+        if *dot {
+            dotvalues += 2_u8.pow(idx as u32 + 1_u32)
+        }
+    }
+
+    dotvalues
+}
+
+/// Segments of a position, to be combined for `display_segments()` (experimental).
+///
+/// The bit of each segment is assumed, it has not been verified on hardware yet.
+pub struct Segment;
+
+impl Segment {
+    /// Top
+    pub const A: u8 = 0x01;
+    /// Top right
+    pub const B: u8 = 0x02;
+    /// Bottom right
+    pub const C: u8 = 0x04;
+    /// Bottom
+    pub const D: u8 = 0x08;
+    /// Bottom left
+    pub const E: u8 = 0x10;
+    /// Top left
+    pub const F: u8 = 0x20;
+    /// Middle
+    pub const G: u8 = 0x40;
+}
+
+/// Content of a position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Cell {
+    /// Digit or character code
+    Char(u8),
+    /// Custom segment pattern
+    Segments(u8),
+}
+
+/// Cell of a blank position
+pub(crate) const BLANK: Cell = Cell::Char(b' ');
+
+/// Last known state of the display, `None` when unknown
 #[derive(Debug, Default)]
-pub struct TWIDisplay<I2C> {
+struct Cache {
+    brightness: Option<u8>,
+    mode: Option<Mode>,
+    dots: Option<u8>,
+    /// Content last written to each position
+    cells: [Option<Cell>; 4],
+}
+
+/// TWIDisplay driver, that holds the I2C bus instance and the I2C address used,
+/// and optionally a delay provider used between retries
+#[derive(Debug, Default)]
+pub struct TWIDisplay<I2C, D = NoDelay> {
     /// The concrete I2C device implementation.
     i2c: I2C,
     dev_addr: u8,
+    /// Address written to the device, active after the next power cycle
+    pending_addr: Option<u8>,
+    /// Used to skip writes that would not change anything
+    cache: Cache,
+    /// How failed writes are retried, `None` to fail on the first error
+    retry: Option<RetryPolicy>,
+    retry_stats: RetryStats,
+    /// Counters of the I2C traffic
+    stats: Stats,
+    delay: D,
 }
 
-impl<I2C, E> TWIDisplay<I2C>
+impl<I2C, E> TWIDisplay<I2C, NoDelay>
 where
     I2C: Write<Error = E> + WriteRead<Error = E>,
 {
     /// Create a new instance of the TWIDisplay driver.    
     pub fn new(i2c: I2C, dev_addr: u8) -> Self {
-        TWIDisplay { i2c, dev_addr }
+        TWIDisplay {
+            i2c,
+            dev_addr,
+            pending_addr: None,
+            cache: Cache::default(),
+            retry: None,
+            retry_stats: RetryStats::default(),
+            stats: Stats::default(),
+            delay: NoDelay,
+        }
+    }
+}
+
+impl<I2C, D, E> TWIDisplay<I2C, D>
+where
+    I2C: Write<Error = E> + WriteRead<Error = E>,
+    D: DelayMs<u16>,
+{
+    /// Retry failed writes according to the policy, waiting between the attempts with the given delay provider
+    pub fn with_retry<D2>(self, policy: RetryPolicy, delay: D2) -> TWIDisplay<I2C, D2>
+    where
+        D2: DelayMs<u16>,
+    {
+        TWIDisplay {
+            i2c: self.i2c,
+            dev_addr: self.dev_addr,
+            pending_addr: self.pending_addr,
+            cache: self.cache,
+            retry: Some(policy),
+            retry_stats: self.retry_stats,
+            stats: self.stats,
+            delay,
+        }
+    }
+
+    /// Change the retry policy, `None` disables retrying
+    pub fn set_retry_policy(&mut self, policy: Option<RetryPolicy>) {
+        self.retry = policy;
+    }
+
+    /// Get the number of retried and failed writes
+    pub fn retry_stats(&self) -> RetryStats {
+        self.retry_stats
+    }
+
+    /// Reset the number of retried and failed writes
+    pub fn reset_retry_stats(&mut self) {
+        self.retry_stats = RetryStats::default();
+    }
+
+    /// Get the counters of the I2C traffic
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Reset the counters of the I2C traffic
+    pub fn reset_stats(&mut self) {
+        self.stats = Stats::default();
     }
 
     /// Destroy driver instance, return I2C bus instance.
@@ -317,41 +1121,104 @@ where
         self.i2c
     }
 
-    /// Write data to the I2C bus
-    fn write(&mut self, payload: &[u8]) -> Result<(), Error<E>> {
-        self.i2c.write(self.dev_addr, payload).map_err(Error::I2C)
+    /// Destroy driver instance, return I2C bus and delay provider instances.
+    pub fn destroy_with_delay(self) -> (I2C, D) {
+        (self.i2c, self.delay)
     }
 
-    /*
+    /// Write data to the I2C bus, retrying according to the retry policy
+    fn write(&mut self, payload: &[u8]) -> Result<(), Error<E>> {
+        let attempts = self.retry.map_or(1, |p| p.max_attempts.max(1));
+        let mut attempt = 1;
+
+        loop {
+            let result = self.i2c.write(self.dev_addr, payload);
+            self.stats.record_write(payload, result.is_ok());
+
+            match result {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt >= attempts => {
+                    if self.retry.is_some() {
+                        self.retry_stats.failures += 1;
+                    }
+                    return Err(Error::I2C(e));
+                }
+                Err(_) => {
+                    if let Some(policy) = self.retry {
+                        self.delay.delay_ms(policy.backoff(attempt));
+                    }
+                    self.retry_stats.retries += 1;
+                    attempt += 1;
+                }
+            }
+        }
+    }
 
-    DOESN'T SEEM TO WORK - NEED TO TEST MORE
+    /// Check that the device acknowledges its address, by reading the firmware revision register.
+    /// Only the acknowledgement matters, the value read is ignored.
+    fn probe(&mut self) -> Result<(), Error<E>> {
+        self.read(Register::FIRMWARE_REV).map(|_| ())
+    }
 
+    // NOT VERIFIED ON HARDWARE: THE ORIGINAL READ DIDN'T SEEM TO WORK - NEED TO TEST MORE
     /// Read data from the I2C bus
     fn read(&mut self, register: u8) -> Result<u8, Error<E>> {
         let mut data = [0];
-        self.i2c
-        .write_read(self.dev_addr, &[register], &mut data)
-        .map_err(Error::I2C)
-        .and(Ok(data[0]))
+        let result = self.i2c.write_read(self.dev_addr, &[register], &mut data);
+        self.stats.record_read(register, result.is_ok());
+        result.map_err(Error::I2C).and(Ok(data[0]))
     }
 
-    /// Read the firmware revision number (currently 1)
+    /// Read the firmware revision number (currently 1).
+    ///
+    /// __NOTE:__ reading from the display has not been verified on hardware yet.
     pub fn get_firmware_rev(&mut self) -> Result<u8, Error<E>> {
         let data = self.read(Register::FIRMWARE_REV)?;
         Ok(data)
     }
 
-    /// Read the number of digits
+    /// Read the number of digits.
+    ///
+    /// __NOTE:__ reading from the display has not been verified on hardware yet.
     pub fn get_number_digits(&mut self) -> Result<u8, Error<E>> {
         let data = self.read(Register::NUMBER_DIGITS)?;
         Ok(data)
     }
 
-     */
+    /// Wait until the display answers after boot, for at most `timeout_ms` milliseconds.
+    ///
+    /// Every few milliseconds the firmware revision register (0x8A) is read, as a `write_read`
+    /// of one byte, until the display acknowledges its address; the value read is ignored.
+    /// If it doesn't answer before the timeout, `Error::Timeout` is returned.
+    pub fn wait_ready<D2>(&mut self, delay: &mut D2, timeout_ms: u32) -> Result<(), Error<E>>
+    where
+        D2: DelayMs<u16>,
+    {
+        let mut waited: u32 = 0;
+
+        loop {
+            if self.probe().is_ok() {
+                return Ok(());
+            }
+            if waited >= timeout_ms {
+                return Err(Error::Timeout);
+            }
+            delay.delay_ms(READY_POLL_MS);
+            waited += READY_POLL_MS as u32;
+        }
+    }
+
+    /// Forget the last known state of the display, so that the next commands are sent
+    /// even if they seem redundant. Use it after the display lost power.
+    pub fn invalidate(&mut self) {
+        self.cache = Cache::default();
+    }
 
     /// Clear the display
     pub fn clear_display(&mut self) -> Result<(), Error<E>> {
         self.write(&[Register::CLEAR_DISPLAY])?;
+        self.cache.cells = [Some(BLANK); 4];
+        self.cache.dots = None;
         Ok(())
     }
 
@@ -360,55 +1227,138 @@ where
     // SEEMS TO WORK OK UP TO 0x39
     // -- USE ADDRESS 0x00 TO RESET IN CASE OF PROBLEMS
 
-    /// Set I2C address, defaults to 0x12
+    /// Set I2C address, defaults to 0x12.
+    /// Reserved addresses (0x00-0x07) and addresses from 0x40 upwards are rejected with `Error::AddressOutOfRange`.
     pub fn set_address(&mut self, address: u8) -> Result<(), Error<E>> {
-        //let mut dev_address = DEFAULT_ADDRESS;
-        match address {
-            //a if a < 0x7f => self.write(&[Register::I2C_ADDRESS_SETTING, a])?,
-            a if a < 0x40 => self.write(&[Register::I2C_ADDRESS_SETTING, a])?,
-            _ => (),
+        if !(SCAN_FIRST_ADDRESS..=LAST_SETTABLE_ADDRESS).contains(&address) {
+            return Err(Error::AddressOutOfRange);
+        }
+        self.write(&[Register::I2C_ADDRESS_SETTING, address])?;
+        Ok(())
+    }
+
+    /// Get the I2C address currently used by the driver
+    pub fn address(&self) -> u8 {
+        self.dev_addr
+    }
+
+    /// Get the address written with `begin_address_change()` or `recover_address()`,
+    /// which is waiting for a power cycle
+    pub fn pending_address(&self) -> Option<u8> {
+        self.pending_addr
+    }
+
+    /// Start changing the I2C address: validate it and send it to the device.
+    ///
+    /// The driver keeps using the current address until the display is power cycled
+    /// and `complete_address_change()` is called.
+    pub fn begin_address_change(&mut self, address: u8) -> Result<(), Error<E>> {
+        self.set_address(address)?;
+        self.pending_addr = Some(address);
+        Ok(())
+    }
+
+    /// Finish the address change after the display was power cycled.
+    ///
+    /// The driver switches to the new address and checks that the device acknowledges it,
+    /// reading the firmware revision register as `wait_ready()` does. If it doesn't, the previous address is restored,
+    /// the change stays pending and the bus error is returned.
+    pub fn complete_address_change(&mut self) -> Result<u8, Error<E>> {
+        let new_addr = self.pending_addr.ok_or(Error::NoAddressChangePending)?;
+        let old_addr = self.dev_addr;
+
+        self.dev_addr = new_addr;
+        if let Err(e) = self.probe() {
+            self.dev_addr = old_addr;
+            return Err(e);
         }
+
+        self.pending_addr = None;
+        self.invalidate();
+        Ok(new_addr)
+    }
+
+    /// Cancel a pending address change (the device keeps whatever it was sent last)
+    pub fn cancel_address_change(&mut self) {
+        self.pending_addr = None;
+    }
+
+    /// Recover a device that doesn't answer on its address (e.g. after setting it to 0x40 or above)
+    /// by sending a new address through the general call address 0x00.
+    ///
+    /// As with `begin_address_change()`, the display must be power cycled
+    /// and `complete_address_change()` called afterwards.
+    pub fn recover_address(&mut self, address: u8) -> Result<(), Error<E>> {
+        if !(SCAN_FIRST_ADDRESS..=LAST_SETTABLE_ADDRESS).contains(&address) {
+            return Err(Error::AddressOutOfRange);
+        }
+        let payload = [Register::I2C_ADDRESS_SETTING, address];
+        let result = self.i2c.write(GENERAL_CALL_ADDRESS, &payload);
+        self.stats.record_write(&payload, result.is_ok());
+        result.map_err(Error::I2C)?;
+        self.pending_addr = Some(address);
         Ok(())
     }
 
     /// Show the current I2C address on the display
     pub fn display_address(&mut self) -> Result<(), Error<E>> {
         self.write(&[Register::DISPLAY_ADDRESS])?;
+        self.cache.cells = [None; 4];
+        self.cache.dots = None;
         Ok(())
     }
 
+    /// Get the last brightness set by the driver, `None` if unknown
+    pub fn brightness(&self) -> Option<u8> {
+        self.cache.brightness
+    }
+
     /// Set display brightness (0 - 255, 127 is 50%)
     pub fn set_brightness(&mut self, brightness: u8) -> Result<(), Error<E>> {
+        if self.cache.brightness == Some(brightness) {
+            return Ok(());
+        }
+        self.cache.brightness = None;
         self.write(&[Register::BRIGHTNESS_SETTING, brightness])?;
+        self.cache.brightness = Some(brightness);
         Ok(())
     }
 
     /// Display the dots, with boolean switches (true is on, false is off)
-
-    // dots are numbered 1,2,3,4 from the left, and they correspond to bits
-    // so 0b0000_0010 is bit 1, dot 1, 0b0000_1000 is bit 3, dot 3 and so on
-
     pub fn display_dots(&mut self, dots: [bool; 4]) -> Result<(), Error<E>> {
-        let mut dotvalues: u8 = 0;
+        self.write_dots(dot_mask(dots))
+    }
 
-        for (idx, dot) in dots.iter().enumerate() {
-            match dot {
-                true => dotvalues += 2_u8.pow(idx as u32 + 1_u32),
-                false => (),
-            }
+    /// Write the dots register, unless it already has this value
+    pub(crate) fn write_dots(&mut self, dotvalues: u8) -> Result<(), Error<E>> {
+        if self.cache.dots == Some(dotvalues) {
+            return Ok(());
         }
-
+        self.cache.dots = None;
         self.write(&[Register::DOTS, dotvalues])?;
+        self.cache.dots = Some(dotvalues);
         Ok(())
     }
 
+    /// Last value written to the dots register, `None` if unknown
+    pub(crate) fn dots_register(&self) -> Option<u8> {
+        self.cache.dots
+    }
+
+    /// Byte last written at a position, `None` if unknown
+    pub(crate) fn cell(&self, position: u8) -> Option<Cell> {
+        self.cache.cells.get(position as usize).copied().flatten()
+    }
+
     /// Send a digit to the display without specifying the position
     pub fn send_digit(&mut self, number: u8) -> Result<(), Error<E>> {
         if number > 9 {
-            return Err(Error::InvalidInputData);
+            return Err(Error::InvalidDigit);
         } else {
             self.write(&[number])?
         };
+        // content is shifted depending on the mode
+        self.cache.cells = [None; 4];
         Ok(())
     }
 
@@ -417,10 +1367,12 @@ where
         // TO DO: include hex digits:
         // 0x00 - 0x0f: Displays a single digit 0-9 or hexadecimal digit A-F.
 
-        if position > 3 || digit > 9 {
-            return Err(Error::InvalidInputData);
+        if position > 3 {
+            return Err(Error::InvalidPosition);
+        } else if digit > 9 {
+            return Err(Error::InvalidDigit);
         } else {
-            self.write(&[Register::POSITION_SETTING, position, digit])?
+            self.write_cell(position, Cell::Char(digit))?
         };
 
         Ok(())
@@ -430,7 +1382,7 @@ where
     // TO DO: ADD A BOOLEAN SWITCH "with_leading_zeros"
     pub fn display_number(&mut self, number: u16) -> Result<(), Error<E>> {
         if number > 9999 {
-            return Err(Error::InvalidInputData);
+            return Err(Error::ValueOutOfRange);
         }
 
         let digits = TWIDisplay::<I2C>::get_digits(number);
@@ -446,7 +1398,13 @@ where
     pub fn send_char(&mut self, ch: char) -> Result<(), Error<E>> {
         // TO DO: restrict to 0x0g - 0x79
 
+        if !ch.is_ascii() {
+            return Err(Error::UnsupportedChar);
+        }
+
         self.write(&[ch as u8])?;
+        // content is shifted depending on the mode
+        self.cache.cells = [None; 4];
         Ok(())
     }
 
@@ -455,13 +1413,41 @@ where
         // TO DO: restrict to 0x0g - 0x79
 
         if position > 3 {
-            return Err(Error::InvalidInputData);
+            return Err(Error::InvalidPosition);
+        } else if !ch.is_ascii() {
+            return Err(Error::UnsupportedChar);
         } else {
-            self.write(&[Register::POSITION_SETTING, position, ch as u8])?;
+            self.write_cell(position, Cell::Char(ch as u8))?;
         };
         Ok(())
     }
 
+    // NOT VERIFIED ON HARDWARE: THE FRAME [0x84, POSITION, SEGMENTS] AND THE BIT ORDER OF `Segment`
+    // ARE ASSUMED - NEED TO TEST MORE
+
+    /// Light up the chosen segments at position P, e.g. `Segment::A | Segment::D` (experimental function)
+    pub fn display_segments(&mut self, position: u8, segments: u8) -> Result<(), Error<E>> {
+        if position > 3 {
+            return Err(Error::InvalidPosition);
+        }
+        self.write_cell(position, Cell::Segments(segments))
+    }
+
+    /// Write a digit, character or segment pattern at a position, unless it's already there
+    pub(crate) fn write_cell(&mut self, position: u8, value: Cell) -> Result<(), Error<E>> {
+        let cell = &mut self.cache.cells[position as usize];
+        if *cell == Some(value) {
+            return Ok(());
+        }
+        *cell = None;
+        match value {
+            Cell::Char(ch) => self.write(&[Register::POSITION_SETTING, position, ch])?,
+            Cell::Segments(segments) => self.write(&[Register::CUSTOM_CHAR, position, segments])?,
+        }
+        self.cache.cells[position as usize] = Some(value);
+        Ok(())
+    }
+
     /// Send text to the display
     pub fn send_text(&mut self, text: &str) -> Result<(), Error<E>> {
         for ch in text.chars() {
@@ -473,7 +1459,7 @@ where
     /// Display time in HH:MM format, with an optional dot between them
     pub fn display_time(&mut self, hours: u8, minutes: u8, dot: bool) -> Result<(), Error<E>> {
         if hours > 23 || minutes > 59 {
-            return Err(Error::InvalidInputData);
+            return Err(Error::ValueOutOfRange);
         } else {
             let time_value = (hours as u16) * 100 + minutes as u16;
 
@@ -488,6 +1474,38 @@ where
         Ok(())
     }
 
+    /// Display two values from 0 to 99 side by side, e.g. a score `12.07`, with or without the dot between them.
+    /// The left value is padded with a blank, the right one with a zero, and a value above 99 is shown as `--`.
+    pub fn display_pair(&mut self, left: u8, right: u8, separator: bool) -> Result<(), Error<E>> {
+        self.display_pair_with_format(left, right, separator, PairFormat::default())
+    }
+
+    /// Display two values from 0 to 99 side by side, with the given padding and overflow marker
+    pub fn display_pair_with_format(
+        &mut self,
+        left: u8,
+        right: u8,
+        separator: bool,
+        format: PairFormat,
+    ) -> Result<(), Error<E>> {
+        for (start, value, padding) in [(0, left, format.left), (2, right, format.right)] {
+            if value > 99 {
+                self.display_char(start, format.overflow)?;
+                self.display_char(start + 1, format.overflow)?;
+                continue;
+            }
+
+            match (value / 10, padding) {
+                (0, Padding::Blank) => self.display_char(start, ' ')?,
+                (tens, _) => self.display_digit(start, tens)?,
+            }
+            self.display_digit(start + 1, value % 10)?;
+        }
+
+        // separator at second position
+        self.display_dots([false, separator, false, false])
+    }
+
     // TO DO: add display_date(month, day, format) function
     // format can be MMDD or DDMM
     // no leading zeros?
@@ -501,30 +1519,21 @@ where
         format: DateFormat,
         dot: bool,
     ) -> Result<(), Error<E>> {
-        if month > 12 || month < 1 {
-            return Err(Error::InvalidInputData);
-        } else if day < 1 {
-            return Err(Error::InvalidInputData);
-        } else if (month == 1
-            || month == 3
-            || month == 5
-            || month == 7
-            || month == 8
-            || month == 10
-            || month == 12)
-            && day > 31
-        {
-            return Err(Error::InvalidInputData);
-        } else if (month == 4 || month == 6 || month == 9 || month == 11) && day > 30 {
-            return Err(Error::InvalidInputData);
-        } else if month == 2 && day > 29 {
-            // no checking for leap years
-            return Err(Error::InvalidInputData);
+        // no checking for leap years
+        let days_in_month = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 => 29,
+            _ => return Err(Error::ValueOutOfRange),
+        };
+
+        if day < 1 || day > days_in_month {
+            return Err(Error::ValueOutOfRange);
         }
 
         let date_number: u16 = match format {
-            DDMM => day as u16 * 100 + month as u16,
-            MMDD => month as u16 * 100 + day as u16,
+            DateFormat::DDMM => day as u16 * 100 + month as u16,
+            DateFormat::MMDD => month as u16 * 100 + day as u16,
         };
 
         self.display_number(date_number)?;
@@ -537,97 +1546,118 @@ where
         Ok(())
     }
 
+    /// Get the last mode set by the driver, `None` if unknown (e.g. before `set_mode()` or after `invalidate()`)
+    pub fn mode(&self) -> Option<Mode> {
+        self.cache.mode
+    }
+
+    /// Run `f` in the given mode, then set the previous mode back.
+    ///
+    /// The previous mode must be known (see `mode()`): otherwise nothing is sent and
+    /// `Error::UnknownMode` is returned, call `set_mode()` first.
+    pub fn with_mode<F, R>(&mut self, mode: Mode, f: F) -> Result<R, Error<E>>
+    where
+        F: FnOnce(&mut Self) -> Result<R, Error<E>>,
+    {
+        let previous = self.cache.mode.ok_or(Error::UnknownMode)?;
+        self.set_mode(mode)?;
+
+        let result = f(self);
+
+        let restored = self.set_mode(previous);
+        // an error of `f` comes first
+        if result.is_ok() {
+            restored?;
+        }
+        result
+    }
+
+    /// Send text to the display in the given mode, then set the previous mode back (see `with_mode()`)
+    pub fn send_text_with_mode(&mut self, text: &str, mode: Mode) -> Result<(), Error<E>> {
+        self.with_mode(mode, |display| display.send_text(text))
+    }
+
     /// Set the display mode: Scroll or Rotate (see documentation)
     pub fn set_mode(&mut self, mode: Mode) -> Result<(), Error<E>> {
+        if self.cache.mode == Some(mode) {
+            return Ok(());
+        }
+        self.cache.mode = None;
         match mode {
             Mode::Rotate => self.write(&[Register::MODE_SETTING, 0])?,
             Mode::Scroll => self.write(&[Register::MODE_SETTING, 1])?,
         }
+        self.cache.mode = Some(mode);
         Ok(())
     }
 
-    /// Display data with units (temperature, humidity) and defined thresholds
-    fn display_data(
+    /// Display a measurement with its unit, with thresholds given by the policy.
+    ///
+    /// The value is shown without leading zeros, followed by the suffix of the unit if it has one.
+    /// Values outside the range of the unit are shown as `----`; values beyond the thresholds
+    /// (those of the policy, or the defaults of the unit) are shown with the labels of the policy.
+    pub fn display_measurement(
         &mut self,
-        data: i16,
-        unit: char,
-        lo_thresh: Option<i16>,
-        hi_thresh: Option<i16>,
-        min_val: i16,
-        max_val: i16,
+        value: i16,
+        unit: Unit,
+        policy: &mut ThresholdPolicy,
     ) -> Result<(), Error<E>> {
-        let mut min_limit = -99;
-        let mut max_limit = 999;
-
-        // check if limits can be accepted, if not reset to -99/999
-        if min_val > (-100) {
-            min_limit = min_val
-        }
-
-        if max_val < 1000 {
-            max_limit = max_val
-        }
-
-        // thresholds initialized as min/max limits
-        let mut lo_th: i16 = min_limit;
-        let mut hi_th: i16 = max_limit;
+        let (min_val, max_val) = unit.range();
+        let (default_lo, default_hi) = unit.default_thresholds();
 
-        if let Some(val) = lo_thresh {
-            lo_th = val
-        }
-
-        if let Some(val) = hi_thresh {
-            hi_th = val
-        }
+        policy.evaluate(value, min_val, max_val, default_lo, default_hi);
 
-        // display -LL- and -HH- for data exceding thresholds,
+        // display ----, -LL- and -HH- (or the labels of the policy) for data exceding limits or thresholds,
         // e.g. -20 and +50 for a temperature sensor
 
-        if data < min_val || data > max_val {
-            for (pos, ch) in "----".chars().enumerate() {
-                self.display_char(pos as u8, ch)?
-            }
-        } else if data < lo_th {
-            for (pos, ch) in "-LL-".chars().enumerate() {
-                self.display_char(pos as u8, ch)?
-            }
-        } else if data > hi_th {
-            for (pos, ch) in "-HH-".chars().enumerate() {
-                self.display_char(pos as u8, ch)?
+        if let Some(label) = policy.label() {
+            for (pos, ch) in label.iter().enumerate() {
+                self.display_char(pos as u8, *ch)?
             }
         } else {
-            let hundreds: u8 = (data.abs() / 100) as u8;
-            let decimals: u8 = ((data.abs() % 100) / 10) as u8;
-
-            // position 0 (hundreds or minus sign)
-            if data < 0 {
-                self.display_char(0, '-')?
-            } else if hundreds == 0 {
-                self.display_char(0, ' ')?
-            } else {
-                self.display_digit(0, hundreds)?
+            let width = match unit.suffix() {
+                Some(_) => 3,
+                None => 4,
+            };
+
+            // digits from the right, no leading zeros, minus sign at position 0
+            let mut magnitude = value.unsigned_abs();
+            for pos in (0..width).rev() {
+                if pos == width - 1 || magnitude > 0 {
+                    self.display_digit(pos, (magnitude % 10) as u8)?;
+                    magnitude /= 10;
+                } else if pos == 0 && value < 0 {
+                    self.display_char(0, '-')?
+                } else {
+                    self.display_char(pos, ' ')?
+                }
             }
 
-            // position 1 (decimals)
-            if (hundreds == 0 || data < 0) && decimals == 0 {
-                self.display_char(1, ' ')?
-                //self.write(&[Register::POSITION_SETTING, 1, ' ' as u8])?
-            } else {
-                self.display_digit(1, decimals)?
+            // position 3 (unit)
+            if let Some(suffix) = unit.suffix() {
+                self.display_char(3, suffix)?;
             }
+        }
 
-            // position 2
-            self.display_digit(2, (data.abs() % 10) as u8)?;
-
-            // position 3 (unit)
-            self.display_char(3, unit)?;
+        if let Some(position) = policy.alarm_dot_position() {
+            if position > 3 {
+                return Err(Error::InvalidPosition);
+            }
+            let mut dots = [false; 4];
+            dots[position as usize] = true;
+            let alarm = dot_mask(dots);
+            let current = self.dots_register().unwrap_or(0);
+
+            match policy.state() {
+                ThresholdState::Normal => self.write_dots(current & !alarm)?,
+                _ => self.write_dots(current | alarm)?,
+            }
         }
 
         Ok(())
     }
 
     /// Display temperature between -99 and 999 with a chosen unit, with lower and upper threshold
-
     pub fn display_temperature(
         &mut self,
         temperature: i16,
@@ -635,25 +1665,212 @@ where
         lo_thresh: Option<i16>,
         hi_thresh: Option<i16>,
     ) -> Result<(), Error<E>> {
-        let temp_unit = match unit {
-            TempUnits::Celsius => 'C',
-            TempUnits::Fahrenheit => 'F',
-        };
+        let mut policy = ThresholdPolicy::new().thresholds(lo_thresh, hi_thresh);
 
-        self.display_data(temperature, temp_unit, lo_thresh, hi_thresh, -99, 999)?;
+        self.display_temperature_with_policy(temperature, unit, &mut policy)?;
 
         Ok(())
     }
 
-    /// Display humidity in range 0-100, with lower and upper threshold.
+    /// Display temperature between -99 and 999 with a chosen unit, with thresholds given by the policy
+    pub fn display_temperature_with_policy(
+        &mut self,
+        temperature: i16,
+        unit: TempUnits,
+        policy: &mut ThresholdPolicy,
+    ) -> Result<(), Error<E>> {
+        self.display_measurement(temperature, unit.into(), policy)?;
+
+        Ok(())
+    }
+
+    /// Display temperature given in tenths of a degree with one decimal place, e.g. `23.5C`,
+    /// converting it from the unit of the reading to the unit to display.
+    ///
+    /// Values between -9.9 and 99.9 are shown with the decimal place, using the dot at the second position.
+    /// Other values are rounded to whole degrees and shown as with `display_temperature()`.
+    pub fn display_temperature_decimal(
+        &mut self,
+        tenths: i16,
+        unit: TempUnits,
+        display_unit: TempUnits,
+    ) -> Result<(), Error<E>> {
+        let tenths = unit.convert_tenths(tenths, display_unit);
+
+        if !(-99..=999).contains(&tenths) {
+            let mut policy = ThresholdPolicy::new();
+            let degrees = div_round(tenths as i32, 10) as i16;
+            self.display_dots([false, false, false, false])?;
+            return self.display_temperature_with_policy(degrees, display_unit, &mut policy);
+        }
+
+        let whole = tenths.abs() / 10;
+
+        // position 0 (tens or minus sign)
+        if tenths < 0 {
+            self.display_char(0, '-')?
+        } else if whole < 10 {
+            self.display_char(0, ' ')?
+        } else {
+            self.display_digit(0, (whole / 10) as u8)?
+        }
+
+        // position 1 (units, followed by the dot)
+        self.display_digit(1, (whole % 10) as u8)?;
+
+        // position 2 (tenths)
+        self.display_digit(2, (tenths.abs() % 10) as u8)?;
+
+        // position 3 (unit)
+        self.display_char(3, display_unit.symbol())?;
+
+        self.display_dots([false, true, false, false])?; // dot at second position
+
+        Ok(())
+    }
+
+    /// Display temperature with one decimal place, see `display_temperature_decimal()`
+    pub fn display_temperature_f32(
+        &mut self,
+        temperature: f32,
+        unit: TempUnits,
+        display_unit: TempUnits,
+    ) -> Result<(), Error<E>> {
+        // checks NaN as well
+        if !(temperature > -3000.0 && temperature < 3000.0) {
+            return Err(Error::ValueOutOfRange);
+        }
+
+        let scaled = temperature * 10.0;
+        let tenths = if scaled < 0.0 {
+            scaled - 0.5
+        } else {
+            scaled + 0.5
+        } as i16;
+
+        self.display_temperature_decimal(tenths, unit, display_unit)
+    }
+
+    /// Display a value scaled with an SI prefix (n, u, m, k, M, G) so that it fits, e.g. `12.3k`, `1.2M` or `999m`.
+    ///
+    /// Up to three positions are used for the value and the dot register for the decimal point;
+    /// the prefix is shown at the last position. Values too large even for the largest prefix
+    /// are shown with the overflow marker of the format.
+    pub fn display_si(&mut self, value: f32, format: SiFormat) -> Result<(), Error<E>> {
+        let (chars, dot) = format.render(value);
+
+        for (pos, ch) in chars.iter().enumerate() {
+            match ch.to_digit(10) {
+                Some(digit) => self.display_digit(pos as u8, digit as u8)?,
+                None => self.display_char(pos as u8, *ch)?,
+            }
+        }
+
+        let mut dots = [false; 4];
+        if let Some(position) = dot {
+            dots[position as usize] = true;
+        }
+        self.display_dots(dots)?;
+
+        Ok(())
+    }
+
+    /// Display a horizontal bar filled in proportion to `value` out of `max`.
+    ///
+    /// Each position shows two steps, its left and then both vertical pairs of segments,
+    /// so the bar has 8 steps. Values above `max` show a full bar.
+    ///
+    /// __NOTE:__ experimental, the bar is made of segment patterns (see `display_segments()`).
+    pub fn display_bar(&mut self, value: u32, max: u32) -> Result<(), Error<E>> {
+        if max == 0 {
+            return Err(Error::ValueOutOfRange);
+        }
+
+        let value = value.min(max) as u64;
+        let steps = ((value * 8 + max as u64 / 2) / max as u64) as u8;
+
+        for pos in 0..4u8 {
+            let segments = match steps {
+                s if s >= pos * 2 + 2 => Segment::B | Segment::C | Segment::E | Segment::F,
+                s if s == pos * 2 + 1 => Segment::E | Segment::F,
+                _ => 0,
+            };
+            self.display_segments(pos, segments)?
+        }
+
+        Ok(())
+    }
+
+    /// Display `value` out of `max` as a percentage, e.g. ` 42P`. Values above `max` show as `100P`.
+    pub fn display_percentage(&mut self, value: u32, max: u32) -> Result<(), Error<E>> {
+        if max == 0 {
+            return Err(Error::ValueOutOfRange);
+        }
+
+        let value = value.min(max) as u64;
+        let percent = ((value * 100 + max as u64 / 2) / max as u64) as i16;
+
+        self.display_measurement(percent, Unit::Percent, &mut ThresholdPolicy::new())
+    }
+
+    /// Display an error code from 0 to 999 as `E` followed by the right-aligned number, e.g. `E 42`
+    pub fn display_error(&mut self, code: u16) -> Result<(), Error<E>> {
+        if code > 999 {
+            return Err(Error::ValueOutOfRange);
+        }
+
+        let mut chars = [' '; 4];
+        chars[0] = 'E';
+        let mut data = code;
+        for pos in (1..4).rev() {
+            chars[pos] = (b'0' + (data % 10) as u8) as char;
+            data /= 10;
+            if data == 0 {
+                break;
+            }
+        }
 
+        self.display_chars(chars)
+    }
+
+    /// Display a device status: `On`, `OFF`, `Err` or `Good`
+    pub fn display_status(&mut self, status: Status) -> Result<(), Error<E>> {
+        self.display_chars(status.text())
+    }
+
+    /// Display a message of four characters, writing every position and turning the dots off,
+    /// so nothing of the previous content is left
+    fn display_chars(&mut self, chars: [char; 4]) -> Result<(), Error<E>> {
+        for (pos, ch) in chars.iter().enumerate() {
+            match ch.to_digit(10) {
+                Some(digit) => self.display_digit(pos as u8, digit as u8)?,
+                None => self.display_char(pos as u8, *ch)?,
+            }
+        }
+        self.display_dots([false; 4])
+    }
+
+    /// Display humidity in range 0-100, with lower and upper threshold.
     pub fn display_humidity(
         &mut self,
         humidity: i16,
         lo_thresh: Option<i16>,
         hi_thresh: Option<i16>,
     ) -> Result<(), Error<E>> {
-        self.display_data(humidity, 'H', lo_thresh, hi_thresh, 0, 100)?;
+        let mut policy = ThresholdPolicy::new().thresholds(lo_thresh, hi_thresh);
+
+        self.display_humidity_with_policy(humidity, &mut policy)?;
+
+        Ok(())
+    }
+
+    /// Display humidity in range 0-100, with thresholds given by the policy.
+    pub fn display_humidity_with_policy(
+        &mut self,
+        humidity: i16,
+        policy: &mut ThresholdPolicy,
+    ) -> Result<(), Error<E>> {
+        self.display_measurement(humidity, Unit::Humidity, policy)?;
 
         Ok(())
     }
@@ -663,11 +1880,11 @@ where
         let mut data = number;
         let mut digits = [0u8; 4];
         digits[0] = (data / 1000) as u8;
-        data = data % 1000;
+        data %= 1000;
         digits[1] = (data / 100) as u8;
-        data = data % 100;
+        data %= 100;
         digits[2] = (data / 10) as u8;
-        data = data % 10;
+        data %= 10;
         digits[3] = data as u8;
         digits
     }
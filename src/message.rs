@@ -0,0 +1,223 @@
+//! Priority queue of timed messages, restoring the previous content once it is empty
+
+use heapless::Vec;
+
+use crate::hal::blocking::delay::DelayMs;
+use crate::hal::blocking::i2c::{Write, WriteRead};
+use crate::{Cell, Error, TWIDisplay, BLANK};
+
+/// Text shown for a given time, e.g. `Err3` or `OPEN`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Message {
+    text: [char; 4],
+    priority: u8,
+    remaining_ms: u32,
+}
+
+impl Message {
+    /// Create a new message from up to four ASCII characters (padded with blanks), shown for `duration_ms` milliseconds
+    pub fn new(text: &str, priority: u8, duration_ms: u32) -> Self {
+        let mut chars = [' '; 4];
+        for (slot, ch) in chars.iter_mut().zip(text.chars()) {
+            *slot = ch;
+        }
+        Message {
+            text: chars,
+            priority,
+            remaining_ms: duration_ms,
+        }
+    }
+
+    /// Text of the message
+    pub fn text(&self) -> [char; 4] {
+        self.text
+    }
+
+    /// Priority of the message, higher ones are shown first
+    pub fn priority(&self) -> u8 {
+        self.priority
+    }
+}
+
+/// Queue of up to `N` messages shown one at a time over the normal content.
+///
+/// The message with the highest priority is shown first (in order of arrival for equal priorities),
+/// and a message with a higher priority preempts the one shown. When the queue is empty,
+/// the content and dots from before the first message are restored. Positions the driver
+/// doesn't know the content of are left blank.
+#[derive(Debug, Clone)]
+pub struct MessageQueue<const N: usize> {
+    queue: Vec<Message, N>,
+    shown: Option<Message>,
+    saved_cells: [Option<Cell>; 4],
+    saved_dots: Option<u8>,
+}
+
+impl<const N: usize> Default for MessageQueue<N> {
+    fn default() -> Self {
+        MessageQueue {
+            queue: Vec::new(),
+            shown: None,
+            saved_cells: [None; 4],
+            saved_dots: None,
+        }
+    }
+}
+
+impl<const N: usize> MessageQueue<N> {
+    /// Create a new empty queue
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check if a message is shown; the normal content shouldn't be written meanwhile
+    pub fn is_showing(&self) -> bool {
+        self.shown.is_some()
+    }
+
+    /// Message currently shown
+    pub fn shown(&self) -> Option<&Message> {
+        self.shown.as_ref()
+    }
+
+    /// Number of messages waiting
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Check if no message is waiting
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Add a message, shown from the next update.
+    ///
+    /// When the queue is full, the waiting message with the lowest priority is dropped for one with a higher priority;
+    /// otherwise the new message is given back.
+    pub fn push(&mut self, message: Message) -> Result<(), Message> {
+        if let Err(message) = self.queue.push(message) {
+            let lowest = self
+                .queue
+                .iter()
+                .enumerate()
+                .rev()
+                .min_by_key(|(_, queued)| queued.priority)
+                .map(|(idx, _)| idx);
+            match lowest {
+                Some(idx) if self.queue[idx].priority < message.priority => {
+                    self.queue.remove(idx);
+                    self.queue.push(message)?;
+                }
+                _ => return Err(message),
+            }
+        }
+        Ok(())
+    }
+
+    /// Move the queue forward by `elapsed_ms` milliseconds, showing the next message or restoring the content when due
+    pub fn update<I2C, D, E>(
+        &mut self,
+        display: &mut TWIDisplay<I2C, D>,
+        elapsed_ms: u32,
+    ) -> Result<(), Error<E>>
+    where
+        I2C: Write<Error = E> + WriteRead<Error = E>,
+        D: DelayMs<u16>,
+    {
+        if let Some(shown) = &mut self.shown {
+            shown.remaining_ms = shown.remaining_ms.saturating_sub(elapsed_ms);
+            if shown.remaining_ms == 0 {
+                self.shown = None;
+            }
+        }
+
+        if let Some(next) = self.next_index() {
+            let preempts = match &self.shown {
+                Some(shown) => self.queue[next].priority > shown.priority,
+                None => true,
+            };
+            if preempts {
+                let message = self.queue.remove(next);
+                match self.shown.replace(message) {
+                    // goes first among its priority; there is room, one message was just taken out
+                    Some(preempted) => {
+                        let _ = self.queue.insert(0, preempted);
+                    }
+                    // the content is saved once, before the first of consecutive messages
+                    None if self.saved_dots.is_none() => self.save(display),
+                    None => (),
+                }
+                return self.render(display, message);
+            }
+        }
+
+        if self.shown.is_none() {
+            self.restore(display)?;
+        }
+        Ok(())
+    }
+
+    /// Drop all the messages and restore the content
+    pub fn clear<I2C, D, E>(&mut self, display: &mut TWIDisplay<I2C, D>) -> Result<(), Error<E>>
+    where
+        I2C: Write<Error = E> + WriteRead<Error = E>,
+        D: DelayMs<u16>,
+    {
+        self.queue.clear();
+        self.shown = None;
+        self.restore(display)
+    }
+
+    /// Index of the waiting message to show next
+    fn next_index(&self) -> Option<usize> {
+        self.queue
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|(_, queued)| queued.priority)
+            .map(|(idx, _)| idx)
+    }
+
+    fn save<I2C, D, E>(&mut self, display: &TWIDisplay<I2C, D>)
+    where
+        I2C: Write<Error = E> + WriteRead<Error = E>,
+        D: DelayMs<u16>,
+    {
+        for (pos, saved) in self.saved_cells.iter_mut().enumerate() {
+            *saved = Some(display.cell(pos as u8).unwrap_or(BLANK));
+        }
+        self.saved_dots = Some(display.dots_register().unwrap_or(0));
+    }
+
+    fn render<I2C, D, E>(
+        &mut self,
+        display: &mut TWIDisplay<I2C, D>,
+        message: Message,
+    ) -> Result<(), Error<E>>
+    where
+        I2C: Write<Error = E> + WriteRead<Error = E>,
+        D: DelayMs<u16>,
+    {
+        display.write_dots(0)?;
+        for (pos, ch) in message.text.iter().enumerate() {
+            display.display_char(pos as u8, *ch)?;
+        }
+        Ok(())
+    }
+
+    fn restore<I2C, D, E>(&mut self, display: &mut TWIDisplay<I2C, D>) -> Result<(), Error<E>>
+    where
+        I2C: Write<Error = E> + WriteRead<Error = E>,
+        D: DelayMs<u16>,
+    {
+        for (pos, saved) in self.saved_cells.iter_mut().enumerate() {
+            if let Some(value) = saved.take() {
+                display.write_cell(pos as u8, value)?;
+            }
+        }
+        if let Some(dots) = self.saved_dots.take() {
+            display.write_dots(dots)?;
+        }
+        Ok(())
+    }
+}
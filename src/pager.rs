@@ -0,0 +1,168 @@
+//! Dashboard pages shown in turn for their own dwell times, preempted by alert pages by priority
+
+use crate::hal::blocking::delay::DelayMs;
+use crate::hal::blocking::i2c::{Write, WriteRead};
+use crate::{Error, TWIDisplay};
+
+/// Content shown by a `Pager`, implemented for closures taking the display
+pub trait Page<I2C, D, E> {
+    /// Show the page; called on every update while the page is shown, so it can stay current
+    fn render(&mut self, display: &mut TWIDisplay<I2C, D>) -> Result<(), Error<E>>;
+}
+
+impl<I2C, D, E, F> Page<I2C, D, E> for F
+where
+    F: FnMut(&mut TWIDisplay<I2C, D>) -> Result<(), Error<E>>,
+{
+    fn render(&mut self, display: &mut TWIDisplay<I2C, D>) -> Result<(), Error<E>> {
+        self(display)
+    }
+}
+
+/// Alert shown instead of the rotation
+#[derive(Debug, Clone, Copy)]
+struct Interrupt {
+    alert: usize,
+    priority: u8,
+    remaining_ms: Option<u32>,
+}
+
+/// Rotates `N` pages, each shown for its own dwell time, and holds `A` alert pages.
+///
+/// An alert preempts the rotation until it expires or is dismissed; the rotation then
+/// continues with the page it left. The pager keeps the alert pages, so each of them can be
+/// raised again and again. The display is cleared whenever another page is shown.
+pub struct Pager<'p, I2C, D, E, const N: usize, const A: usize> {
+    pages: [(&'p mut dyn Page<I2C, D, E>, u32); N],
+    alerts: [&'p mut dyn Page<I2C, D, E>; A],
+    current: usize,
+    elapsed_ms: u32,
+    interrupt: Option<Interrupt>,
+    changed: bool,
+}
+
+impl<'p, I2C, D, E, const N: usize, const A: usize> Pager<'p, I2C, D, E, N, A>
+where
+    I2C: Write<Error = E> + WriteRead<Error = E>,
+    D: DelayMs<u16>,
+{
+    /// Create a new pager from pages with their dwell times in milliseconds, and alert pages
+    pub fn new(
+        pages: [(&'p mut dyn Page<I2C, D, E>, u32); N],
+        alerts: [&'p mut dyn Page<I2C, D, E>; A],
+    ) -> Self {
+        Pager {
+            pages,
+            alerts,
+            current: 0,
+            elapsed_ms: 0,
+            interrupt: None,
+            changed: true,
+        }
+    }
+
+    /// Index of the page of the rotation that is shown (or will be shown after the alert)
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// Check if an alert is shown instead of the rotation
+    pub fn is_interrupted(&self) -> bool {
+        self.interrupt.is_some()
+    }
+
+    /// Index of the alert shown instead of the rotation, if any
+    pub fn alert(&self) -> Option<usize> {
+        self.interrupt.map(|interrupt| interrupt.alert)
+    }
+
+    /// Show the page at `index` on the next update, restarting its dwell time
+    pub fn show(&mut self, index: usize) -> Result<(), Error<E>> {
+        if index >= N {
+            return Err(Error::InvalidInputData);
+        }
+        self.current = index;
+        self.elapsed_ms = 0;
+        self.changed = true;
+        Ok(())
+    }
+
+    /// Show the next page on the next update
+    pub fn next(&mut self) {
+        if N > 0 {
+            self.current = (self.current + 1) % N;
+        }
+        self.elapsed_ms = 0;
+        self.changed = true;
+    }
+
+    /// Preempt the rotation with the alert page at `alert` for `duration_ms` milliseconds, `None` until dismissed.
+    ///
+    /// An alert with a lower priority than the one shown is ignored; returns whether the alert is shown.
+    pub fn interrupt(
+        &mut self,
+        alert: usize,
+        priority: u8,
+        duration_ms: Option<u32>,
+    ) -> Result<bool, Error<E>> {
+        if alert >= A {
+            return Err(Error::InvalidInputData);
+        }
+        if let Some(shown) = &self.interrupt {
+            if priority < shown.priority {
+                return Ok(false);
+            }
+        }
+
+        self.interrupt = Some(Interrupt {
+            alert,
+            priority,
+            remaining_ms: duration_ms,
+        });
+        self.changed = true;
+        Ok(true)
+    }
+
+    /// Remove the alert, continuing the rotation on the next update
+    pub fn dismiss(&mut self) {
+        if self.interrupt.take().is_some() {
+            self.changed = true;
+        }
+    }
+
+    /// Move the pager forward by `elapsed_ms` milliseconds and render the page shown.
+    /// The first call shows the first page.
+    pub fn update(
+        &mut self,
+        display: &mut TWIDisplay<I2C, D>,
+        elapsed_ms: u32,
+    ) -> Result<(), Error<E>> {
+        if let Some(interrupt) = &mut self.interrupt {
+            match interrupt.remaining_ms {
+                Some(remaining) if remaining <= elapsed_ms => self.dismiss(),
+                Some(remaining) => interrupt.remaining_ms = Some(remaining - elapsed_ms),
+                None => (),
+            }
+        } else if !self.changed && N > 0 {
+            self.elapsed_ms = self.elapsed_ms.saturating_add(elapsed_ms);
+            while self.elapsed_ms >= self.pages[self.current].1.max(1) {
+                self.elapsed_ms -= self.pages[self.current].1.max(1);
+                self.current = (self.current + 1) % N;
+                self.changed = true;
+            }
+        }
+
+        if self.changed {
+            display.clear_display()?;
+            self.changed = false;
+        }
+
+        match self.interrupt {
+            Some(interrupt) => self.alerts[interrupt.alert].render(display),
+            None => match self.pages.get_mut(self.current) {
+                Some((page, _)) => page.render(display),
+                None => Ok(()),
+            },
+        }
+    }
+}
@@ -0,0 +1,43 @@
+//! Two values side by side, e.g. scores shown as `12.07`
+
+/// What fills the tens position of a value below 10
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Padding {
+    /// Leading zero, e.g. `07`
+    Zero,
+    /// Blank, e.g. ` 7`
+    Blank,
+}
+
+/// Settings of `display_pair_with_format()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PairFormat {
+    /// Padding of the left value (positions 0 and 1)
+    pub left: Padding,
+    /// Padding of the right value (positions 2 and 3)
+    pub right: Padding,
+    /// Shown at both positions of a value above 99
+    pub overflow: char,
+}
+
+impl Default for PairFormat {
+    /// Blank on the left, zero on the right, `--` on overflow
+    fn default() -> Self {
+        PairFormat {
+            left: Padding::Blank,
+            right: Padding::Zero,
+            overflow: '-',
+        }
+    }
+}
+
+impl PairFormat {
+    /// Create a new format with the given paddings and overflow marker
+    pub fn new(left: Padding, right: Padding, overflow: char) -> Self {
+        PairFormat {
+            left,
+            right,
+            overflow,
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Retrying I2C writes that were not acknowledged
+
+use crate::hal::blocking::delay::DelayMs;
+
+/// How failed writes are retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one
+    pub max_attempts: u8,
+    /// Delay before the first retry in milliseconds, doubled for every next retry
+    pub backoff_ms: u16,
+}
+
+impl RetryPolicy {
+    /// Create a new retry policy
+    pub fn new(max_attempts: u8, backoff_ms: u16) -> Self {
+        RetryPolicy {
+            max_attempts,
+            backoff_ms,
+        }
+    }
+
+    /// Delay before the given retry (1 is the first retry)
+    pub(crate) fn backoff(&self, retry: u8) -> u16 {
+        let shift = retry.saturating_sub(1).min(15);
+        self.backoff_ms.saturating_mul(1 << shift)
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, starting with 1 ms between them
+    fn default() -> Self {
+        RetryPolicy::new(3, 1)
+    }
+}
+
+/// Number of retried and failed writes
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RetryStats {
+    /// Writes that were attempted again
+    pub retries: u32,
+    /// Writes that failed after all the attempts
+    pub failures: u32,
+}
+
+/// Delay provider that doesn't wait, used when no delay was given to the driver
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoDelay;
+
+impl DelayMs<u16> for NoDelay {
+    fn delay_ms(&mut self, _ms: u16) {}
+}
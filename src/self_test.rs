@@ -0,0 +1,228 @@
+//! Self-test and burn-in of the segments, brightness and modes, e.g. for production test
+
+use crate::hal::blocking::delay::DelayMs;
+use crate::hal::blocking::i2c::{Write, WriteRead};
+use crate::{Error, Mode, Segment, TWIDisplay};
+
+/// Segments in the order they are walked
+const SEGMENTS: [u8; 7] = [
+    Segment::A,
+    Segment::B,
+    Segment::C,
+    Segment::D,
+    Segment::E,
+    Segment::F,
+    Segment::G,
+];
+
+/// Text sent one character at a time in each mode
+const MODE_TEXT: &str = "1234";
+
+/// Step of the self-test, passed to the test jig once it is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestStep {
+    /// Every segment and dot lit: `8.8.8.8.`
+    AllSegments,
+    /// Only one segment lit, at the given position
+    Segment {
+        /// Position 0-3
+        position: u8,
+        /// One of the `Segment` constants
+        segment: u8,
+    },
+    /// Only the dot at the given position lit
+    Dot(u8),
+    /// `8.8.8.8.` at the given brightness
+    Brightness(u8),
+    /// `1234` sent one character at a time in the given mode
+    Mode(Mode),
+}
+
+/// Confirmation of each step of the self-test, implemented for closures taking the step
+pub trait TestJig {
+    /// Check the step shown on the display, `false` stops the test
+    fn confirm(&mut self, step: TestStep) -> bool;
+}
+
+impl<F> TestJig for F
+where
+    F: FnMut(TestStep) -> bool,
+{
+    fn confirm(&mut self, step: TestStep) -> bool {
+        self(step)
+    }
+}
+
+/// Blocking self-test: lights every segment, walks each segment and dot, sweeps the brightness
+/// and sends text in both modes, asking the test jig to confirm every step.
+///
+/// Afterwards the display is cleared and the brightness and mode are set back, if the driver knows them.
+///
+/// __NOTE:__ the segment walk uses segment patterns, which are experimental (see `display_segments()`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SelfTest {
+    dwell_ms: u16,
+    brightness_step: u8,
+    sweep_ms: u16,
+}
+
+impl Default for SelfTest {
+    /// 500 ms per step, brightness in steps of 15 shown for 20 ms each
+    fn default() -> Self {
+        SelfTest {
+            dwell_ms: 500,
+            brightness_step: 15,
+            sweep_ms: 20,
+        }
+    }
+}
+
+impl SelfTest {
+    /// Create a new self-test with the default timing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how long each step is shown before the jig is asked, also used between characters in the mode steps
+    pub fn dwell(mut self, dwell_ms: u16) -> Self {
+        self.dwell_ms = dwell_ms;
+        self
+    }
+
+    /// Set the brightness sweep from 0 to 255: increment, and how long each level is shown
+    pub fn sweep(mut self, brightness_step: u8, sweep_ms: u16) -> Self {
+        self.brightness_step = brightness_step.max(1);
+        self.sweep_ms = sweep_ms;
+        self
+    }
+
+    /// Run the test, returning the first step the jig didn't confirm, `None` if all passed
+    pub fn run<I2C, D, E, D2, J>(
+        &self,
+        display: &mut TWIDisplay<I2C, D>,
+        delay: &mut D2,
+        jig: &mut J,
+    ) -> Result<Option<TestStep>, Error<E>>
+    where
+        I2C: Write<Error = E> + WriteRead<Error = E>,
+        D: DelayMs<u16>,
+        D2: DelayMs<u16>,
+        J: TestJig,
+    {
+        let brightness = display.brightness();
+        let mode = display.mode();
+
+        let failed = self.steps(display, delay, jig)?;
+
+        display.clear_display()?;
+        display.display_dots([false; 4])?;
+        if let Some(brightness) = brightness {
+            display.set_brightness(brightness)?;
+        }
+        if let Some(mode) = mode {
+            display.set_mode(mode)?;
+        }
+
+        Ok(failed)
+    }
+
+    fn steps<I2C, D, E, D2, J>(
+        &self,
+        display: &mut TWIDisplay<I2C, D>,
+        delay: &mut D2,
+        jig: &mut J,
+    ) -> Result<Option<TestStep>, Error<E>>
+    where
+        I2C: Write<Error = E> + WriteRead<Error = E>,
+        D: DelayMs<u16>,
+        D2: DelayMs<u16>,
+        J: TestJig,
+    {
+        display.set_brightness(255)?;
+        all_segments(display)?;
+        if !self.confirm(delay, jig, TestStep::AllSegments) {
+            return Ok(Some(TestStep::AllSegments));
+        }
+
+        display.display_dots([false; 4])?;
+        for position in 0..4 {
+            for segment in SEGMENTS.iter() {
+                for pos in 0..4 {
+                    let segments = if pos == position { *segment } else { 0 };
+                    display.display_segments(pos, segments)?;
+                }
+                let step = TestStep::Segment {
+                    position,
+                    segment: *segment,
+                };
+                if !self.confirm(delay, jig, step) {
+                    return Ok(Some(step));
+                }
+            }
+        }
+
+        display.clear_display()?;
+        for position in 0..4 {
+            let mut dots = [false; 4];
+            dots[position] = true;
+            display.display_dots(dots)?;
+            let step = TestStep::Dot(position as u8);
+            if !self.confirm(delay, jig, step) {
+                return Ok(Some(step));
+            }
+        }
+
+        all_segments(display)?;
+        let mut level = 0u8;
+        loop {
+            display.set_brightness(level)?;
+            delay.delay_ms(self.sweep_ms);
+            let step = TestStep::Brightness(level);
+            if !jig.confirm(step) {
+                return Ok(Some(step));
+            }
+            if level == 255 {
+                break;
+            }
+            level = level.saturating_add(self.brightness_step);
+        }
+
+        for mode in [Mode::Scroll, Mode::Rotate] {
+            display.clear_display()?;
+            display.display_dots([false; 4])?;
+            display.set_mode(mode)?;
+            for ch in MODE_TEXT.chars() {
+                display.send_char(ch)?;
+                delay.delay_ms(self.dwell_ms);
+            }
+            let step = TestStep::Mode(mode);
+            if !jig.confirm(step) {
+                return Ok(Some(step));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Wait for the dwell time and ask the jig to confirm the step
+    fn confirm<D2, J>(&self, delay: &mut D2, jig: &mut J, step: TestStep) -> bool
+    where
+        D2: DelayMs<u16>,
+        J: TestJig,
+    {
+        delay.delay_ms(self.dwell_ms);
+        jig.confirm(step)
+    }
+}
+
+/// Show `8.8.8.8.`
+fn all_segments<I2C, D, E>(display: &mut TWIDisplay<I2C, D>) -> Result<(), Error<E>>
+where
+    I2C: Write<Error = E> + WriteRead<Error = E>,
+    D: DelayMs<u16>,
+{
+    for pos in 0..4 {
+        display.display_digit(pos, 8)?;
+    }
+    display.display_dots([true; 4])
+}
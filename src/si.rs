@@ -0,0 +1,126 @@
+//! Auto-ranging of values with SI prefixes, e.g. `12.3k` or `999m`
+
+/// Prefixes from nano to giga, shown at the last position (micro as `u`, none as blank)
+const PREFIXES: [char; 7] = ['n', 'u', 'm', ' ', 'k', 'M', 'G'];
+
+/// Index of the blank prefix (no scaling)
+const NO_PREFIX: usize = 3;
+
+/// Settings of `display_si()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SiFormat {
+    /// Maximum number of decimal places, used as far as they fit
+    pub precision: u8,
+    /// Shown when the value is too large (or not a number)
+    pub overflow: [char; 4],
+}
+
+impl Default for SiFormat {
+    /// One decimal place, `----` on overflow
+    fn default() -> Self {
+        SiFormat {
+            precision: 1,
+            overflow: ['-'; 4],
+        }
+    }
+}
+
+impl SiFormat {
+    /// Create a new format with the given precision and overflow marker
+    pub fn new(precision: u8, overflow: [char; 4]) -> Self {
+        SiFormat {
+            precision,
+            overflow,
+        }
+    }
+
+    /// Characters of the four positions and the position of the dot, if any
+    pub(crate) fn render(&self, value: f32) -> ([char; 4], Option<u8>) {
+        if !value.is_finite() {
+            return (self.overflow, None);
+        }
+
+        let mut negative = value < 0.0;
+        let mut scaled = if negative { -value } else { value };
+        let mut prefix = NO_PREFIX;
+
+        while scaled >= 1000.0 && prefix < PREFIXES.len() - 1 {
+            scaled /= 1000.0;
+            prefix += 1;
+        }
+        while scaled > 0.0 && scaled < 1.0 && prefix > 0 {
+            scaled *= 1000.0;
+            prefix -= 1;
+        }
+
+        if scaled >= 1000.0 {
+            return (self.overflow, None);
+        }
+        if scaled < 1.0 {
+            // zero, or too small for the prefixes
+            scaled = 0.0;
+            prefix = NO_PREFIX;
+            negative = false;
+        }
+
+        // the minus sign takes one of the three positions for digits
+        let cells = if negative { 2 } else { 3 };
+
+        let (mantissa, int_digits, decimals) = loop {
+            let int_digits = count_digits(scaled as u32);
+            if int_digits > cells {
+                if prefix == PREFIXES.len() - 1 {
+                    return (self.overflow, None);
+                }
+                scaled /= 1000.0;
+                prefix += 1;
+                continue;
+            }
+
+            let decimals = (self.precision as usize).min(cells - int_digits);
+            let pow = 10u32.pow(decimals as u32);
+            let mantissa = (scaled * pow as f32 + 0.5) as u32;
+
+            // rounding added a digit, e.g. 9.96 to 10.0
+            if count_digits(mantissa / pow) > int_digits {
+                scaled = mantissa as f32 / pow as f32;
+                continue;
+            }
+
+            break (mantissa, int_digits, decimals);
+        };
+
+        let mut chars = [' '; 4];
+        chars[3] = PREFIXES[prefix];
+
+        let total = int_digits + decimals;
+        let start = 3 - total;
+        let mut data = mantissa;
+        for pos in (start..3).rev() {
+            chars[pos] = (b'0' + (data % 10) as u8) as char;
+            data /= 10;
+        }
+
+        if negative {
+            chars[0] = '-';
+        }
+
+        let dot = match decimals {
+            0 => None,
+            _ => Some((start + int_digits - 1) as u8),
+        };
+
+        (chars, dot)
+    }
+}
+
+/// Number of decimal digits of a number (1 for zero)
+fn count_digits(number: u32) -> usize {
+    let mut digits = 1;
+    let mut data = number / 10;
+    while data > 0 {
+        digits += 1;
+        data /= 10;
+    }
+    digits
+}
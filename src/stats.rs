@@ -0,0 +1,76 @@
+//! Bus statistics, to help telling wiring problems from firmware problems
+
+/// Kind of I2C transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionKind {
+    /// Command sent to the display
+    Write,
+    /// Register read from the display
+    Read,
+}
+
+/// The last failed transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LastError {
+    /// Kind of the transaction that failed
+    pub kind: TransactionKind,
+    /// Command or register byte of the transaction
+    pub command: u8,
+    /// Number of the transaction that failed (counted from the last reset)
+    pub transaction: u32,
+}
+
+/// Counters of the I2C traffic of one driver, each attempt of a retried write counts separately
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    /// Transactions attempted
+    pub transactions: u32,
+    /// Bytes acknowledged by the display
+    pub bytes_sent: u32,
+    /// Bytes read from the display
+    pub bytes_received: u32,
+    /// Failed writes
+    pub write_errors: u32,
+    /// Failed reads
+    pub read_errors: u32,
+    /// The last failed transaction, if any
+    pub last_error: Option<LastError>,
+}
+
+impl Stats {
+    /// Total number of failed transactions
+    pub fn errors(&self) -> u32 {
+        self.write_errors.saturating_add(self.read_errors)
+    }
+
+    /// Count a write of `payload`
+    pub(crate) fn record_write(&mut self, payload: &[u8], ok: bool) {
+        self.transactions = self.transactions.saturating_add(1);
+        if ok {
+            self.bytes_sent = self.bytes_sent.saturating_add(payload.len() as u32);
+        } else {
+            self.write_errors = self.write_errors.saturating_add(1);
+            self.last_error = Some(LastError {
+                kind: TransactionKind::Write,
+                command: payload.first().copied().unwrap_or(0),
+                transaction: self.transactions,
+            });
+        }
+    }
+
+    /// Count a read of one register
+    pub(crate) fn record_read(&mut self, register: u8, ok: bool) {
+        self.transactions = self.transactions.saturating_add(1);
+        if ok {
+            self.bytes_sent = self.bytes_sent.saturating_add(1);
+            self.bytes_received = self.bytes_received.saturating_add(1);
+        } else {
+            self.read_errors = self.read_errors.saturating_add(1);
+            self.last_error = Some(LastError {
+                kind: TransactionKind::Read,
+                command: register,
+                transaction: self.transactions,
+            });
+        }
+    }
+}
@@ -0,0 +1,26 @@
+//! Status messages shared by devices using this driver
+
+/// Device status shown by `display_status()`, left-aligned and padded with blanks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// `On`
+    On,
+    /// `OFF`
+    Off,
+    /// `Err`
+    Err,
+    /// `Good`
+    Good,
+}
+
+impl Status {
+    /// Characters of the four positions
+    pub fn text(self) -> [char; 4] {
+        match self {
+            Status::On => ['O', 'n', ' ', ' '],
+            Status::Off => ['O', 'F', 'F', ' '],
+            Status::Err => ['E', 'r', 'r', ' '],
+            Status::Good => ['G', 'o', 'o', 'd'],
+        }
+    }
+}
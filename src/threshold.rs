@@ -0,0 +1,154 @@
+//! Thresholds with hysteresis for sensor readings
+
+/// Where the last reading was with respect to the thresholds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThresholdState {
+    /// Between the thresholds, the value is shown
+    Normal,
+    /// Below the lower threshold
+    Low,
+    /// Above the upper threshold
+    High,
+    /// Outside of the range the display can show
+    OutOfLimits,
+}
+
+/// Thresholds used by `display_measurement()`, `display_temperature_with_policy()` and `display_humidity_with_policy()`.
+///
+/// A reading that crossed a threshold has to come back by the hysteresis before the value is shown again,
+/// so readings close to the threshold don't jump between the value and the label.
+/// The policy keeps this state, so the same instance must be used for consecutive readings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThresholdPolicy {
+    lo_thresh: Option<i16>,
+    hi_thresh: Option<i16>,
+    hysteresis: i16,
+    low_label: [char; 4],
+    high_label: [char; 4],
+    blink: bool,
+    alarm_dot: Option<u8>,
+    state: ThresholdState,
+    blink_hidden: bool,
+}
+
+impl Default for ThresholdPolicy {
+    fn default() -> Self {
+        ThresholdPolicy {
+            lo_thresh: None,
+            hi_thresh: None,
+            hysteresis: 0,
+            low_label: ['-', 'L', 'L', '-'],
+            high_label: ['-', 'H', 'H', '-'],
+            blink: false,
+            alarm_dot: None,
+            state: ThresholdState::Normal,
+            blink_hidden: false,
+        }
+    }
+}
+
+impl ThresholdPolicy {
+    /// Create a new policy without thresholds (the limits of the display are used) and without hysteresis
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the lower and upper threshold
+    pub fn thresholds(mut self, lo_thresh: Option<i16>, hi_thresh: Option<i16>) -> Self {
+        self.lo_thresh = lo_thresh;
+        self.hi_thresh = hi_thresh;
+        self
+    }
+
+    /// Set how far back a reading must come before the value is shown again
+    pub fn hysteresis(mut self, hysteresis: i16) -> Self {
+        self.hysteresis = hysteresis.max(0);
+        self
+    }
+
+    /// Set the labels shown below the lower and above the upper threshold (default `-LL-` and `-HH-`)
+    pub fn labels(mut self, low_label: [char; 4], high_label: [char; 4]) -> Self {
+        self.low_label = low_label;
+        self.high_label = high_label;
+        self
+    }
+
+    /// Blink the label when out of range: it is shown and hidden on alternate readings
+    pub fn blink(mut self, blink: bool) -> Self {
+        self.blink = blink;
+        self
+    }
+
+    /// Turn on the dot at the given position (0-3) when out of range, `None` to leave the dots alone
+    pub fn alarm_dot(mut self, position: Option<u8>) -> Self {
+        self.alarm_dot = position;
+        self
+    }
+
+    /// State after the last reading
+    pub fn state(&self) -> ThresholdState {
+        self.state
+    }
+
+    /// Forget the last reading
+    pub fn reset(&mut self) {
+        self.state = ThresholdState::Normal;
+        self.blink_hidden = false;
+    }
+
+    /// Classify a reading, given the limits of the display and the default thresholds
+    pub(crate) fn evaluate(
+        &mut self,
+        value: i16,
+        min_val: i16,
+        max_val: i16,
+        default_lo: i16,
+        default_hi: i16,
+    ) -> ThresholdState {
+        let lo = self.lo_thresh.unwrap_or(default_lo);
+        let hi = self.hi_thresh.unwrap_or(default_hi);
+
+        self.state = match self.state {
+            _ if value < min_val || value > max_val => ThresholdState::OutOfLimits,
+            ThresholdState::High if value > hi.saturating_sub(self.hysteresis) => {
+                ThresholdState::High
+            }
+            ThresholdState::Low if value < lo.saturating_add(self.hysteresis) => {
+                ThresholdState::Low
+            }
+            _ if value > hi => ThresholdState::High,
+            _ if value < lo => ThresholdState::Low,
+            _ => ThresholdState::Normal,
+        };
+
+        self.state
+    }
+
+    /// Label for the current state, `None` when the value is shown
+    pub(crate) fn label(&mut self) -> Option<[char; 4]> {
+        let label = match self.state {
+            ThresholdState::Normal => {
+                self.blink_hidden = false;
+                return None;
+            }
+            ThresholdState::Low => self.low_label,
+            ThresholdState::High => self.high_label,
+            ThresholdState::OutOfLimits => ['-'; 4],
+        };
+
+        if self.blink {
+            let hidden = self.blink_hidden;
+            self.blink_hidden = !hidden;
+            if hidden {
+                return Some([' '; 4]);
+            }
+        }
+
+        Some(label)
+    }
+
+    /// Position of the alarm dot, if any
+    pub(crate) fn alarm_dot_position(&self) -> Option<u8> {
+        self.alarm_dot
+    }
+}
@@ -0,0 +1,76 @@
+//! Units of the values shown with `display_measurement()`
+
+use crate::TempUnits;
+
+/// Unit of a measurement, deciding the suffix, the range and the default thresholds.
+///
+/// Units with a suffix use three digits and show the suffix at the last position,
+/// units without one use all four digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    /// Celsius degrees, suffix `C`, -99 to 999
+    Celsius,
+    /// Fahrenheit degrees, suffix `F`, -99 to 999
+    Fahrenheit,
+    /// Relative humidity, suffix `H`, 0 to 100
+    Humidity,
+    /// Pressure in hPa, no suffix, 0 to 9999, thresholds 300 and 1100
+    Pressure,
+    /// CO2 concentration in ppm, no suffix, 0 to 9999, upper threshold 5000
+    Co2,
+    /// Voltage, suffix `U`, -99 to 999
+    Voltage,
+    /// Current, suffix `A`, -99 to 999
+    Current,
+    /// Percent, suffix `P`, 0 to 100
+    Percent,
+    /// Illuminance in lux, suffix `L`, 0 to 999
+    Lux,
+    /// Any other unit, with the given suffix, -99 to 999
+    Custom(char),
+}
+
+impl Unit {
+    /// Character displayed after the value, `None` if all four digits are used for the value
+    pub fn suffix(self) -> Option<char> {
+        match self {
+            Unit::Celsius => Some('C'),
+            Unit::Fahrenheit => Some('F'),
+            Unit::Humidity => Some('H'),
+            Unit::Pressure | Unit::Co2 => None,
+            Unit::Voltage => Some('U'),
+            Unit::Current => Some('A'),
+            Unit::Percent => Some('P'),
+            Unit::Lux => Some('L'),
+            Unit::Custom(ch) => Some(ch),
+        }
+    }
+
+    /// Lowest and highest value that can be shown, others are shown as `----`
+    pub fn range(self) -> (i16, i16) {
+        match self {
+            Unit::Humidity | Unit::Percent => (0, 100),
+            Unit::Pressure | Unit::Co2 => (0, 9999),
+            Unit::Lux => (0, 999),
+            _ => (-99, 999),
+        }
+    }
+
+    /// Lower and upper threshold used when the policy doesn't set them
+    pub fn default_thresholds(self) -> (i16, i16) {
+        match self {
+            Unit::Pressure => (300, 1100),
+            Unit::Co2 => (0, 5000),
+            _ => self.range(),
+        }
+    }
+}
+
+impl From<TempUnits> for Unit {
+    fn from(unit: TempUnits) -> Self {
+        match unit {
+            TempUnits::Celsius => Unit::Celsius,
+            TempUnits::Fahrenheit => Unit::Fahrenheit,
+        }
+    }
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use akafugu_twidisplay::*;
+use common::{BusError, RecordingBus};
+
+#[test]
+fn address_change_is_completed_after_the_probe() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+
+    display.begin_address_change(0x20).unwrap();
+    assert_eq!(bus.writes(), vec![vec![0x81, 0x20]]);
+    assert_eq!(display.address(), DEFAULT_ADDRESS);
+
+    assert_eq!(display.complete_address_change().unwrap(), 0x20);
+    assert_eq!(display.address(), 0x20);
+    assert_eq!(display.pending_address(), None);
+    assert_eq!(bus.reads(), vec![0x8a]);
+}
+
+#[test]
+fn failed_probe_rolls_back_and_keeps_the_change_pending() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+
+    display.begin_address_change(0x20).unwrap();
+
+    bus.fail_next(1);
+    assert!(matches!(
+        display.complete_address_change(),
+        Err(Error::I2C(BusError))
+    ));
+    assert_eq!(display.address(), DEFAULT_ADDRESS);
+    assert_eq!(display.pending_address(), Some(0x20));
+
+    // the display answers after another power cycle
+    assert_eq!(display.complete_address_change().unwrap(), 0x20);
+    assert_eq!(display.address(), 0x20);
+}
+
+#[test]
+fn nothing_to_complete() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+
+    assert!(matches!(
+        display.complete_address_change(),
+        Err(Error::NoAddressChangePending)
+    ));
+    assert!(bus.reads().is_empty());
+}
+
+#[test]
+fn scan_reads_the_firmware_revision_only() {
+    let mut bus = RecordingBus::new();
+    bus.set_read_value(1);
+
+    let mut found = [0u8; 3];
+    assert_eq!(scan(&mut bus, &mut found), 3);
+    assert_eq!(found, [0x08, 0x09, 0x0a]);
+    assert_eq!(bus.reads(), vec![0x8a; 3]);
+    assert!(bus.writes().is_empty());
+}
+
+#[test]
+fn scan_skips_implausible_revisions() {
+    let mut bus = RecordingBus::new();
+    bus.set_read_value(0xff);
+
+    let mut found = [0u8; 4];
+    assert_eq!(scan(&mut bus, &mut found), 0);
+    assert_eq!(bus.reads().len(), 0x70);
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use akafugu_twidisplay::*;
+use common::RecordingBus;
+
+const CURVE: [(u32, u8); 3] = [(10, 5), (100, 80), (1000, 255)];
+
+struct Sensor(Option<u32>);
+
+impl LightSensor for Sensor {
+    fn lux(&mut self) -> Option<u32> {
+        self.0
+    }
+}
+
+#[test]
+fn curve_is_interpolated_between_points() {
+    let auto = AutoBrightness::new(&CURVE);
+
+    assert_eq!(auto.brightness_for(100), 80);
+    assert_eq!(auto.brightness_for(55), 42);
+    assert_eq!(auto.brightness_for(550), 167);
+    assert_eq!(auto.brightness_for(999), 254);
+
+    // falling curves work the same
+    let falling = [(0, 200), (100, 100)];
+    assert_eq!(AutoBrightness::new(&falling).brightness_for(25), 175);
+}
+
+#[test]
+fn readings_outside_the_curve_are_clamped() {
+    let auto = AutoBrightness::new(&CURVE);
+    assert_eq!(auto.brightness_for(0), 5);
+    assert_eq!(auto.brightness_for(10), 5);
+    assert_eq!(auto.brightness_for(1000), 255);
+    assert_eq!(auto.brightness_for(u32::MAX), 255);
+
+    assert_eq!(AutoBrightness::new(&[]).brightness_for(50), 255);
+    assert_eq!(AutoBrightness::new(&[(20, 30)]).brightness_for(0), 30);
+    assert_eq!(AutoBrightness::new(&[(20, 30)]).brightness_for(50), 30);
+}
+
+#[test]
+fn small_changes_are_ignored() {
+    let mut auto = AutoBrightness::new(&CURVE).hysteresis(10).max_step(0);
+
+    assert_eq!(auto.process(100), 80);
+    // 81 and 89 are within the hysteresis of 80
+    assert_eq!(auto.process(110), 80);
+    assert_eq!(auto.process(150), 80);
+    assert_eq!(auto.target(), Some(80));
+    // 99 is not
+    assert_eq!(auto.process(200), 99);
+    assert_eq!(auto.process(100), 80);
+}
+
+#[test]
+fn brightness_steps_towards_the_target() {
+    let mut auto = AutoBrightness::new(&CURVE).hysteresis(0).max_step(100);
+
+    // the first reading is applied at once
+    assert_eq!(auto.process(0), 5);
+    assert_eq!(auto.process(1000), 105);
+    assert_eq!(auto.target(), Some(255));
+    assert_eq!(auto.process(1000), 205);
+    assert_eq!(auto.process(1000), 255);
+    assert_eq!(auto.process(1000), 255);
+
+    assert_eq!(auto.process(0), 155);
+    assert_eq!(auto.process(0), 55);
+    assert_eq!(auto.process(0), 5);
+}
+
+#[test]
+fn display_follows_the_sensor() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+    let mut auto = AutoBrightness::new(&CURVE);
+
+    assert_eq!(
+        auto.update_from(&mut display, &mut Sensor(None)).unwrap(),
+        None
+    );
+    assert!(bus.writes().is_empty());
+
+    assert_eq!(
+        auto.update_from(&mut display, &mut Sensor(Some(100)))
+            .unwrap(),
+        Some(80)
+    );
+    assert_eq!(bus.writes(), vec![vec![0x80, 80]]);
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use akafugu_twidisplay::*;
+use common::RecordingBus;
+
+#[test]
+fn blink_restores_known_content() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+    display.display_char(0, 'A').unwrap();
+    display.display_char(1, 'B').unwrap();
+
+    let mut blink = Blink::new([true, false, false, false], [false; 4], 1000);
+    blink.update(&mut display, 500).unwrap();
+    assert!(blink.is_hidden());
+    assert_eq!(bus.screen().0, " B  ");
+
+    blink.update(&mut display, 500).unwrap();
+    assert!(!blink.is_hidden());
+    assert_eq!(bus.screen().0, "AB  ");
+}
+
+#[test]
+fn blink_leaves_unknown_content_alone() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+    display.send_text("ABCD").unwrap();
+    bus.clear();
+
+    let mut blink = Blink::new([true, false, false, false], [false; 4], 1000);
+    blink.update(&mut display, 500).unwrap();
+    blink.update(&mut display, 500).unwrap();
+    assert!(bus.writes().iter().all(|w| w.first() != Some(&0x89)));
+
+    display.display_address().unwrap();
+    bus.clear();
+    blink.update(&mut display, 500).unwrap();
+    blink.update(&mut display, 500).unwrap();
+    assert!(bus.writes().iter().all(|w| w.first() != Some(&0x89)));
+}
@@ -0,0 +1,142 @@
+mod common;
+
+use akafugu_twidisplay::*;
+use common::RecordingBus;
+
+#[test]
+fn repeated_writes_are_skipped() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+
+    display.set_brightness(127).unwrap();
+    display.set_brightness(127).unwrap();
+    display.set_mode(Mode::Scroll).unwrap();
+    display.set_mode(Mode::Scroll).unwrap();
+    display.display_dots([true, false, false, false]).unwrap();
+    display.display_dots([true, false, false, false]).unwrap();
+    display.display_digit(1, 7).unwrap();
+    display.display_digit(1, 7).unwrap();
+
+    assert_eq!(
+        bus.writes(),
+        vec![
+            vec![0x80, 127],
+            vec![0x83, 1],
+            vec![0x85, 0b0000_0010],
+            vec![0x89, 1, 7],
+        ]
+    );
+}
+
+#[test]
+fn changed_values_are_written() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+
+    display.display_number(1234).unwrap();
+    bus.clear();
+    display.display_number(1284).unwrap();
+
+    assert_eq!(bus.writes(), vec![vec![0x89, 2, 8]]);
+}
+
+#[test]
+fn clear_display_caches_blank_positions() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+
+    display.display_char(0, 'A').unwrap();
+    display.clear_display().unwrap();
+    bus.clear();
+    display.display_char(0, ' ').unwrap();
+    display.display_char(0, 'A').unwrap();
+
+    assert_eq!(bus.writes(), vec![vec![0x89, 0, b'A']]);
+}
+
+#[test]
+fn send_char_forgets_positions() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+
+    display.display_char(0, 'A').unwrap();
+    display.send_char('B').unwrap();
+    bus.clear();
+    display.display_char(0, 'A').unwrap();
+
+    assert_eq!(bus.writes(), vec![vec![0x89, 0, b'A']]);
+}
+
+#[test]
+fn invalidate_resets_state() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+
+    display.set_brightness(127).unwrap();
+    display.set_mode(Mode::Rotate).unwrap();
+    display.display_dots([false; 4]).unwrap();
+    display.display_digit(0, 1).unwrap();
+    display.invalidate();
+    assert_eq!(display.brightness(), None);
+    assert_eq!(display.mode(), None);
+
+    bus.clear();
+    display.set_brightness(127).unwrap();
+    display.set_mode(Mode::Rotate).unwrap();
+    display.display_dots([false; 4]).unwrap();
+    display.display_digit(0, 1).unwrap();
+
+    assert_eq!(
+        bus.writes(),
+        vec![
+            vec![0x80, 127],
+            vec![0x83, 0],
+            vec![0x85, 0],
+            vec![0x89, 0, 1]
+        ]
+    );
+}
+
+#[test]
+fn failed_write_resets_state() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+
+    display.set_brightness(100).unwrap();
+    display.display_digit(0, 1).unwrap();
+
+    // the device may or may not have received the failed commands
+    bus.fail_next(2);
+    assert!(display.set_brightness(200).is_err());
+    assert!(display.display_digit(0, 2).is_err());
+    assert_eq!(display.brightness(), None);
+
+    bus.clear();
+    display.set_brightness(100).unwrap();
+    display.display_digit(0, 1).unwrap();
+
+    assert_eq!(bus.writes(), vec![vec![0x80, 100], vec![0x89, 0, 1]]);
+}
+
+#[test]
+fn with_mode_sets_the_previous_mode_back() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+
+    // nothing to go back to on a fresh driver
+    assert!(matches!(
+        display.with_mode(Mode::Rotate, |d| d.send_char('A')),
+        Err(Error::UnknownMode)
+    ));
+    assert!(bus.writes().is_empty());
+
+    display.set_mode(Mode::Scroll).unwrap();
+    bus.clear();
+    display.send_text_with_mode("AB", Mode::Rotate).unwrap();
+
+    assert_eq!(display.mode(), Some(Mode::Scroll));
+    assert_eq!(
+        bus.writes(),
+        vec![vec![0x83, 0], vec![b'A'], vec![b'B'], vec![0x83, 1]]
+    );
+}
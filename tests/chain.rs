@@ -0,0 +1,69 @@
+mod common;
+
+use akafugu_twidisplay::*;
+use common::RecordingBus;
+
+fn chain() -> (DisplayChain<RecordingBus, 2>, [RecordingBus; 2]) {
+    let buses = [RecordingBus::new(), RecordingBus::new()];
+    let chain = DisplayChain::new([
+        TWIDisplay::new(buses[0].clone(), 0x12),
+        TWIDisplay::new(buses[1].clone(), 0x13),
+    ]);
+    (chain, buses)
+}
+
+fn screens(buses: &[RecordingBus; 2]) -> (String, String) {
+    (buses[0].screen().0, buses[1].screen().0)
+}
+
+#[test]
+fn number_fills_the_chain_and_overflow_is_rejected() {
+    let (mut chain, buses) = chain();
+
+    chain.display_number(1234567).unwrap();
+    assert_eq!(screens(&buses), ("0123".into(), "4567".into()));
+
+    chain.display_number(99_999_999).unwrap();
+    buses[0].clear();
+    buses[1].clear();
+    assert!(matches!(
+        chain.display_number(100_000_000),
+        Err(Error::ValueOutOfRange)
+    ));
+    assert!(buses[0].writes().is_empty() && buses[1].writes().is_empty());
+}
+
+#[test]
+fn scroll_wraps_around_after_a_blank_gap() {
+    let (mut chain, buses) = chain();
+
+    chain.scroll_text("HELLO", 0).unwrap();
+    assert_eq!(screens(&buses), ("HELL".into(), "O   ".into()));
+    chain.scroll_text("HELLO", 4).unwrap();
+    assert_eq!(screens(&buses), ("O   ".into(), "    ".into()));
+    // text of 5 followed by a gap of 8: the text comes back from the right
+    chain.scroll_text("HELLO", 10).unwrap();
+    assert_eq!(screens(&buses), ("   H".into(), "ELLO".into()));
+    chain.scroll_text("HELLO", 13).unwrap();
+    assert_eq!(screens(&buses), ("HELL".into(), "O   ".into()));
+}
+
+#[test]
+fn dots_are_split_across_the_displays() {
+    let (mut chain, buses) = chain();
+
+    chain
+        .display_dots(&[false, false, false, true, true, false, false, false])
+        .unwrap();
+    assert_eq!(buses[0].writes(), vec![vec![0x85, 0b1_0000]]);
+    assert_eq!(buses[1].writes(), vec![vec![0x85, 0b0_0010]]);
+
+    assert!(matches!(
+        chain.display_dots(&[true; 7]),
+        Err(Error::ValueOutOfRange)
+    ));
+    assert!(matches!(
+        chain.display_dots(&[true; 9]),
+        Err(Error::ValueOutOfRange)
+    ));
+}
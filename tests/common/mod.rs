@@ -0,0 +1,140 @@
+//! Recording bus and delay shared by the integration tests
+
+#![allow(dead_code)]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::i2c::{Write, WriteRead};
+
+/// Error returned by the recording bus when told to fail
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BusError;
+
+#[derive(Debug, Default)]
+struct Log {
+    writes: Vec<Vec<u8>>,
+    reads: Vec<u8>,
+    failed: usize,
+    fail_next: usize,
+    read_value: u8,
+}
+
+/// I2C bus recording the payloads of the successful writes.
+///
+/// Clones share the same log, so a clone can be handed to the driver and inspected from the test.
+#[derive(Debug, Clone, Default)]
+pub struct RecordingBus {
+    log: Rc<RefCell<Log>>,
+}
+
+impl RecordingBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Payloads of the successful writes, oldest first
+    pub fn writes(&self) -> Vec<Vec<u8>> {
+        self.log.borrow().writes.clone()
+    }
+
+    /// Registers of the successful reads, oldest first
+    pub fn reads(&self) -> Vec<u8> {
+        self.log.borrow().reads.clone()
+    }
+
+    /// Number of failed write and read attempts
+    pub fn failed(&self) -> usize {
+        self.log.borrow().failed
+    }
+
+    /// Forget the writes recorded so far
+    pub fn clear(&self) {
+        self.log.borrow_mut().writes.clear();
+    }
+
+    /// Fail the next `count` writes and reads
+    pub fn fail_next(&self, count: usize) {
+        self.log.borrow_mut().fail_next = count;
+    }
+
+    /// Value returned by reads
+    pub fn set_read_value(&self, value: u8) {
+        self.log.borrow_mut().read_value = value;
+    }
+
+    /// Characters at the four positions and the dots register, replaying the positioned writes:
+    /// digits are shown as `0`-`9`, segment patterns as `#`
+    pub fn screen(&self) -> (String, u8) {
+        let mut cells = [' '; 4];
+        let mut dots = 0;
+        for write in self.log.borrow().writes.iter() {
+            match write.as_slice() {
+                [0x82] => cells = [' '; 4],
+                [0x85, mask] => dots = *mask,
+                [0x89, pos, value] if *value < 10 => cells[*pos as usize] = (b'0' + value) as char,
+                [0x89, pos, value] => cells[*pos as usize] = *value as char,
+                [0x84, pos, _] => cells[*pos as usize] = '#',
+                _ => (),
+            }
+        }
+        (cells.iter().collect(), dots)
+    }
+
+    fn take_failure(&self) -> bool {
+        let mut log = self.log.borrow_mut();
+        if log.fail_next > 0 {
+            log.fail_next -= 1;
+            log.failed += 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl Write for RecordingBus {
+    type Error = BusError;
+
+    fn write(&mut self, _address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        if self.take_failure() {
+            return Err(BusError);
+        }
+        self.log.borrow_mut().writes.push(bytes.to_vec());
+        Ok(())
+    }
+}
+
+impl WriteRead for RecordingBus {
+    type Error = BusError;
+
+    fn write_read(
+        &mut self,
+        _address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        if self.take_failure() {
+            return Err(BusError);
+        }
+        self.log.borrow_mut().reads.extend_from_slice(bytes);
+        let value = self.log.borrow().read_value;
+        for byte in buffer.iter_mut() {
+            *byte = value;
+        }
+        Ok(())
+    }
+}
+
+/// Delay provider recording the requested delays
+#[derive(Debug, Default)]
+pub struct RecordingDelay {
+    pub delays: Vec<u16>,
+}
+
+impl DelayMs<u16> for RecordingDelay {
+    fn delay_ms(&mut self, ms: u16) {
+        self.delays.push(ms);
+    }
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use akafugu_twidisplay::*;
+use common::RecordingBus;
+
+#[test]
+fn transition_changes_content_in_the_dark() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+    let mut transition = FadeTransition::new(200, 100);
+
+    assert!(transition
+        .update(&mut display, 50, |d| d.send_char('A'))
+        .unwrap());
+    assert!(transition
+        .update(&mut display, 50, |d| d.send_char('A'))
+        .unwrap());
+    assert_eq!(display.brightness(), Some(0));
+    assert_eq!(bus.writes().last(), Some(&vec![b'A']));
+
+    assert!(transition
+        .update(&mut display, 50, |d| d.send_char('B'))
+        .unwrap());
+    assert!(!transition
+        .update(&mut display, 50, |d| d.send_char('B'))
+        .unwrap());
+    assert!(transition.is_done());
+    assert_eq!(display.brightness(), Some(200));
+    assert!(!bus.writes().contains(&vec![b'B']));
+}
+
+#[test]
+fn zero_duration_transition_still_goes_dark() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+    let mut transition = FadeTransition::new(200, 0);
+
+    assert!(transition
+        .update(&mut display, 0, |d| d.send_char('A'))
+        .unwrap());
+    assert!(!transition
+        .update(&mut display, 0, |d| d.send_char('A'))
+        .unwrap());
+
+    assert_eq!(
+        bus.writes(),
+        vec![vec![0x80, 0], vec![b'A'], vec![0x80, 200]]
+    );
+}
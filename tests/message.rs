@@ -0,0 +1,102 @@
+mod common;
+
+use akafugu_twidisplay::*;
+use common::RecordingBus;
+
+fn shown<const N: usize>(queue: &MessageQueue<N>) -> Option<String> {
+    queue.shown().map(|m| m.text().iter().collect())
+}
+
+#[test]
+fn content_is_restored_after_the_messages() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+    display.display_time(12, 34, true).unwrap();
+    let before = bus.screen();
+
+    let mut queue: MessageQueue<4> = MessageQueue::new();
+    queue.push(Message::new("OPEN", 1, 100)).unwrap();
+    queue.push(Message::new("Err3", 1, 100)).unwrap();
+
+    queue.update(&mut display, 0).unwrap();
+    assert_eq!(bus.screen(), ("OPEN".into(), 0));
+    queue.update(&mut display, 100).unwrap();
+    assert_eq!(bus.screen(), ("Err3".into(), 0));
+    queue.update(&mut display, 100).unwrap();
+    assert!(!queue.is_showing());
+    assert_eq!(bus.screen(), before);
+}
+
+#[test]
+fn higher_priority_preempts_and_the_other_message_resumes() {
+    let mut display = TWIDisplay::new(RecordingBus::new(), DEFAULT_ADDRESS);
+    let mut queue: MessageQueue<4> = MessageQueue::new();
+
+    queue.push(Message::new("OPEN", 1, 100)).unwrap();
+    queue.update(&mut display, 0).unwrap();
+    queue.update(&mut display, 40).unwrap();
+
+    queue.push(Message::new("FIRE", 9, 50)).unwrap();
+    queue.push(Message::new("LOW", 1, 100)).unwrap();
+    queue.update(&mut display, 0).unwrap();
+    assert_eq!(shown(&queue), Some("FIRE".into()));
+
+    // a lower priority doesn't preempt
+    queue.push(Message::new("INFO", 5, 100)).unwrap();
+    queue.update(&mut display, 10).unwrap();
+    assert_eq!(shown(&queue), Some("FIRE".into()));
+
+    queue.update(&mut display, 40).unwrap();
+    assert_eq!(shown(&queue), Some("INFO".into()));
+    queue.update(&mut display, 100).unwrap();
+    // the preempted message goes before the later one of the same priority, with its remaining time
+    assert_eq!(shown(&queue), Some("OPEN".into()));
+    queue.update(&mut display, 59).unwrap();
+    assert_eq!(shown(&queue), Some("OPEN".into()));
+    queue.update(&mut display, 1).unwrap();
+    assert_eq!(shown(&queue), Some("LOW ".into()));
+}
+
+#[test]
+fn full_queue_drops_the_newest_lowest_priority() {
+    let mut display = TWIDisplay::new(RecordingBus::new(), DEFAULT_ADDRESS);
+    let mut queue: MessageQueue<3> = MessageQueue::new();
+
+    queue.push(Message::new("A", 2, 10)).unwrap();
+    queue.push(Message::new("B", 1, 10)).unwrap();
+    queue.push(Message::new("C", 1, 10)).unwrap();
+
+    // not higher than the lowest, given back
+    let rejected = queue.push(Message::new("D", 1, 10)).unwrap_err();
+    assert_eq!(rejected.text(), ['D', ' ', ' ', ' ']);
+
+    // replaces "C", the newest of the lowest priority
+    queue.push(Message::new("E", 3, 10)).unwrap();
+    assert_eq!(queue.len(), 3);
+
+    let mut order = String::new();
+    for _ in 0..3 {
+        queue.update(&mut display, 10).unwrap();
+        order.push(queue.shown().unwrap().text()[0]);
+    }
+    assert_eq!(order, "EAB");
+}
+
+#[test]
+fn clear_restores_the_content() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+    display.display_number(42).unwrap();
+    display.display_dots([false, true, false, false]).unwrap();
+    let before = bus.screen();
+
+    let mut queue: MessageQueue<2> = MessageQueue::new();
+    queue.push(Message::new("HOT", 1, 1000)).unwrap();
+    queue.update(&mut display, 0).unwrap();
+    assert_eq!(bus.screen(), ("HOT ".into(), 0));
+
+    queue.clear(&mut display).unwrap();
+    assert!(queue.is_empty());
+    assert!(!queue.is_showing());
+    assert_eq!(bus.screen(), before);
+}
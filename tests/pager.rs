@@ -0,0 +1,108 @@
+mod common;
+
+use akafugu_twidisplay::*;
+use common::{BusError, RecordingBus};
+
+type Display = TWIDisplay<RecordingBus>;
+
+/// Page showing four characters
+fn page(text: &'static str) -> impl FnMut(&mut Display) -> Result<(), Error<BusError>> {
+    move |d: &mut Display| {
+        for (pos, ch) in text.chars().enumerate() {
+            d.display_char(pos as u8, ch)?
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn rotation_follows_the_dwell_times() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+    let (mut a, mut b) = (page("AAAA"), page("BBBB"));
+    let mut pager: Pager<_, _, _, 2, 0> =
+        Pager::new([(&mut a as &mut dyn Page<_, _, _>, 100), (&mut b, 50)], []);
+
+    pager.update(&mut display, 0).unwrap();
+    assert_eq!(bus.screen().0, "AAAA");
+    pager.update(&mut display, 99).unwrap();
+    assert_eq!(pager.current(), 0);
+    pager.update(&mut display, 1).unwrap();
+    assert_eq!(bus.screen().0, "BBBB");
+    pager.update(&mut display, 50).unwrap();
+    assert_eq!(bus.screen().0, "AAAA");
+
+    // a long tick rolls over several pages, keeping the remainder
+    pager.update(&mut display, 180).unwrap();
+    assert_eq!(pager.current(), 0);
+    pager.update(&mut display, 69).unwrap();
+    assert_eq!(pager.current(), 0);
+    pager.update(&mut display, 1).unwrap();
+    assert_eq!(bus.screen().0, "BBBB");
+}
+
+#[test]
+fn alert_expires_and_can_be_raised_again() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+    let (mut a, mut b, mut open) = (page("AAAA"), page("BBBB"), page("OPEN"));
+    let mut pager = Pager::new(
+        [(&mut a as &mut dyn Page<_, _, _>, 100), (&mut b, 100)],
+        [&mut open as &mut dyn Page<_, _, _>],
+    );
+
+    pager.update(&mut display, 0).unwrap();
+    pager.update(&mut display, 60).unwrap();
+
+    for _ in 0..2 {
+        assert!(pager.interrupt(0, 1, Some(100)).unwrap());
+        pager.update(&mut display, 0).unwrap();
+        assert_eq!(bus.screen().0, "OPEN");
+        // the rotation doesn't move while the alert is shown
+        pager.update(&mut display, 99).unwrap();
+        assert_eq!(pager.alert(), Some(0));
+        pager.update(&mut display, 1).unwrap();
+        assert!(!pager.is_interrupted());
+        assert_eq!(bus.screen().0, "AAAA");
+    }
+
+    // the page keeps the time it was shown before the alerts
+    pager.update(&mut display, 39).unwrap();
+    assert_eq!(pager.current(), 0);
+    pager.update(&mut display, 1).unwrap();
+    assert_eq!(bus.screen().0, "BBBB");
+}
+
+#[test]
+fn lower_priority_alert_is_ignored() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+    let (mut a, mut fire, mut open) = (page("AAAA"), page("FIRE"), page("OPEN"));
+    let mut pager = Pager::new(
+        [(&mut a as &mut dyn Page<_, _, _>, 100)],
+        [
+            &mut fire as &mut dyn Page<_, _, _>,
+            &mut open as &mut dyn Page<_, _, _>,
+        ],
+    );
+
+    assert!(pager.interrupt(0, 5, None).unwrap());
+    assert!(!pager.interrupt(1, 1, None).unwrap());
+    pager.update(&mut display, 1000).unwrap();
+    assert_eq!(pager.alert(), Some(0));
+    assert_eq!(bus.screen().0, "FIRE");
+
+    // equal priority replaces the alert shown
+    assert!(pager.interrupt(1, 5, None).unwrap());
+    pager.update(&mut display, 0).unwrap();
+    assert_eq!(bus.screen().0, "OPEN");
+
+    pager.dismiss();
+    pager.update(&mut display, 0).unwrap();
+    assert_eq!(bus.screen().0, "AAAA");
+
+    assert!(matches!(
+        pager.interrupt(2, 9, None),
+        Err(Error::InvalidInputData)
+    ));
+}
@@ -0,0 +1,33 @@
+mod common;
+
+use akafugu_twidisplay::*;
+use common::{RecordingBus, RecordingDelay};
+
+#[test]
+fn wait_ready_polls_the_firmware_revision() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+    let mut delay = RecordingDelay::default();
+
+    bus.fail_next(2);
+    display.wait_ready(&mut delay, 100).unwrap();
+
+    assert_eq!(bus.failed(), 2);
+    assert_eq!(bus.reads(), vec![0x8a]);
+    assert!(bus.writes().is_empty());
+    assert_eq!(delay.delays.len(), 2);
+}
+
+#[test]
+fn wait_ready_times_out() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+    let mut delay = RecordingDelay::default();
+
+    bus.fail_next(usize::MAX);
+    assert!(matches!(
+        display.wait_ready(&mut delay, 20),
+        Err(Error::Timeout)
+    ));
+    assert!(bus.writes().is_empty());
+}
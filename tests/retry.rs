@@ -0,0 +1,88 @@
+mod common;
+
+use akafugu_twidisplay::*;
+use common::{RecordingBus, RecordingDelay};
+
+#[test]
+fn retried_write_succeeds_with_doubling_backoff() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS)
+        .with_retry(RetryPolicy::new(4, 2), RecordingDelay::default());
+
+    bus.fail_next(2);
+    display.set_brightness(127).unwrap();
+
+    assert_eq!(
+        display.retry_stats(),
+        RetryStats {
+            retries: 2,
+            failures: 0
+        }
+    );
+    assert_eq!(bus.writes(), vec![vec![0x80, 127]]);
+    let (_, delay) = display.destroy_with_delay();
+    assert_eq!(delay.delays, vec![2, 4]);
+}
+
+#[test]
+fn write_fails_after_all_attempts() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS)
+        .with_retry(RetryPolicy::new(4, 2), RecordingDelay::default());
+
+    bus.fail_next(5);
+    assert!(matches!(display.clear_display(), Err(Error::I2C(_))));
+
+    assert_eq!(bus.failed(), 4);
+    assert_eq!(
+        display.retry_stats(),
+        RetryStats {
+            retries: 3,
+            failures: 1
+        }
+    );
+
+    display.reset_retry_stats();
+    assert_eq!(display.retry_stats(), RetryStats::default());
+    let (_, delay) = display.destroy_with_delay();
+    assert_eq!(delay.delays, vec![2, 4, 8]);
+}
+
+#[test]
+fn backoff_saturates() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS)
+        .with_retry(RetryPolicy::new(3, 40_000), RecordingDelay::default());
+
+    bus.fail_next(2);
+    display.clear_display().unwrap();
+
+    let (_, delay) = display.destroy_with_delay();
+    assert_eq!(delay.delays, vec![40_000, u16::MAX]);
+}
+
+#[test]
+fn no_retries_without_policy() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+
+    bus.fail_next(1);
+    assert!(display.clear_display().is_err());
+    display.clear_display().unwrap();
+
+    assert_eq!(bus.failed(), 1);
+    assert_eq!(display.retry_stats(), RetryStats::default());
+}
+
+#[test]
+fn retry_policy_can_be_disabled() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS)
+        .with_retry(RetryPolicy::default(), RecordingDelay::default());
+
+    display.set_retry_policy(None);
+    bus.fail_next(1);
+    assert!(display.clear_display().is_err());
+    assert_eq!(bus.failed(), 1);
+    assert_eq!(display.retry_stats(), RetryStats::default());
+}
@@ -0,0 +1,79 @@
+use std::cell::RefCell;
+
+use akafugu_twidisplay::*;
+use embedded_hal::blocking::i2c::{Write, WriteRead};
+use embedded_hal_mock::I2cMock;
+
+const SENSOR_ADDRESS: u8 = 0x40;
+
+/// Bus proxy in the style of `shared-bus`, handing out access to a bus behind a `RefCell`
+struct Proxy<'a, 'b> {
+    bus: &'a RefCell<I2cMock<'b>>,
+}
+
+impl<'a, 'b> Write for Proxy<'a, 'b> {
+    type Error = embedded_hal_mock::MockError;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.bus.borrow_mut().write(address, bytes)
+    }
+}
+
+impl<'a, 'b> WriteRead for Proxy<'a, 'b> {
+    type Error = embedded_hal_mock::MockError;
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.bus.borrow_mut().write_read(address, bytes, buffer)
+    }
+}
+
+#[test]
+fn borrowed_bus_is_released_for_another_device() {
+    let mut i2c = I2cMock::new();
+
+    let mut display = TWIDisplay::new(I2cRef::new(&mut i2c), DEFAULT_ADDRESS);
+    display.set_brightness(127).unwrap();
+    display.destroy();
+
+    assert_eq!(i2c.get_last_address(), Some(DEFAULT_ADDRESS));
+    assert_eq!(i2c.get_write_data(), &[0x80, 127]);
+
+    i2c.write(SENSOR_ADDRESS, &[0x01]).unwrap();
+    assert_eq!(i2c.get_last_address(), Some(SENSOR_ADDRESS));
+
+    let mut display = TWIDisplay::new(I2cRef::from(&mut i2c), DEFAULT_ADDRESS);
+    display.display_digit(2, 7).unwrap();
+    display.destroy();
+
+    assert_eq!(i2c.get_last_address(), Some(DEFAULT_ADDRESS));
+    assert_eq!(i2c.get_write_data(), &[0x89, 2, 7]);
+}
+
+#[test]
+fn display_and_sensor_share_bus_through_proxies() {
+    let bus = RefCell::new(I2cMock::new());
+    bus.borrow_mut().set_read_data(&[0x55]);
+
+    let mut display = TWIDisplay::new(Proxy { bus: &bus }, DEFAULT_ADDRESS);
+    let mut sensor = Proxy { bus: &bus };
+
+    display.clear_display().unwrap();
+    assert_eq!(bus.borrow().get_last_address(), Some(DEFAULT_ADDRESS));
+    assert_eq!(bus.borrow().get_write_data(), &[0x82]);
+
+    let mut reading = [0];
+    sensor
+        .write_read(SENSOR_ADDRESS, &[0x00], &mut reading)
+        .unwrap();
+    assert_eq!(bus.borrow().get_last_address(), Some(SENSOR_ADDRESS));
+    assert_eq!(reading, [0x55]);
+
+    display.display_char(3, 'P').unwrap();
+    assert_eq!(bus.borrow().get_last_address(), Some(DEFAULT_ADDRESS));
+    assert_eq!(bus.borrow().get_write_data(), &[0x89, 3, b'P']);
+}
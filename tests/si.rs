@@ -0,0 +1,75 @@
+mod common;
+
+use akafugu_twidisplay::*;
+use common::RecordingBus;
+
+/// Text shown for the value, with the dots after the positions they belong to
+fn si(value: f32, format: SiFormat) -> String {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+    display.display_si(value, format).unwrap();
+
+    let (text, dots) = bus.screen();
+    let mut shown = String::new();
+    for (pos, ch) in text.chars().enumerate() {
+        shown.push(ch);
+        if dots & (1 << (pos + 1)) != 0 {
+            shown.push('.');
+        }
+    }
+    shown
+}
+
+#[test]
+fn values_are_scaled_with_prefixes() {
+    let format = SiFormat::default();
+    assert_eq!(si(12_345.0, format), "12.3k");
+    assert_eq!(si(0.999, format), "999m");
+    assert_eq!(si(4.7e-6, format), " 4.7u");
+    assert_eq!(si(3.3e-9, format), " 3.3n");
+    assert_eq!(si(2.5e6, format), " 2.5M");
+    assert_eq!(si(1.0e9, format), " 1.0G");
+    assert_eq!(si(230.0, format), "230 ");
+    assert_eq!(si(5.0, format), " 5.0 ");
+}
+
+#[test]
+fn rounding_moves_to_the_next_prefix() {
+    let format = SiFormat::default();
+    assert_eq!(si(999.96, format), " 1.0k");
+    assert_eq!(si(9.96, format), "10.0 ");
+    assert_eq!(si(999_600.0, format), " 1.0M");
+}
+
+#[test]
+fn precision_is_limited_by_the_positions() {
+    assert_eq!(si(1.234, SiFormat::new(2, ['-'; 4])), "1.23 ");
+    assert_eq!(si(12.345, SiFormat::new(2, ['-'; 4])), "12.3 ");
+    assert_eq!(si(12_345.0, SiFormat::new(0, ['-'; 4])), " 12k");
+}
+
+#[test]
+fn negative_values_keep_the_sign() {
+    let format = SiFormat::default();
+    assert_eq!(si(-1.5, format), "-1.5 ");
+    assert_eq!(si(-12.3, format), "-12 ");
+    assert_eq!(si(-4700.0, format), "-4.7k");
+    assert_eq!(si(-150.0, format), "-0.2k");
+}
+
+#[test]
+fn zero_and_tiny_values() {
+    let format = SiFormat::default();
+    assert_eq!(si(0.0, format), " 0.0 ");
+    assert_eq!(si(1.0e-12, format), " 0.0 ");
+    assert_eq!(si(-1.0e-12, format), " 0.0 ");
+}
+
+#[test]
+fn overflow_shows_the_marker() {
+    let format = SiFormat::new(1, ['O', 'V', 'E', 'R']);
+    assert_eq!(si(1.0e12, format), "OVER");
+    assert_eq!(si(-1.0e12, format), "OVER");
+    assert_eq!(si(f32::NAN, format), "OVER");
+    assert_eq!(si(f32::INFINITY, SiFormat::default()), "----");
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use akafugu_twidisplay::*;
+use common::RecordingBus;
+
+#[test]
+fn traffic_is_counted() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+
+    display.set_brightness(127).unwrap();
+    display.display_digit(0, 1).unwrap();
+    bus.set_read_value(1);
+    assert_eq!(display.get_firmware_rev().unwrap(), 1);
+
+    let stats = display.stats();
+    assert_eq!(stats.transactions, 3);
+    assert_eq!(stats.bytes_sent, 6);
+    assert_eq!(stats.bytes_received, 1);
+    assert_eq!(stats.errors(), 0);
+    assert_eq!(stats.last_error, None);
+}
+
+#[test]
+fn last_failure_is_kept() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+
+    display.clear_display().unwrap();
+    bus.fail_next(1);
+    assert!(display.set_brightness(10).is_err());
+    bus.fail_next(1);
+    assert!(display.get_number_digits().is_err());
+
+    let stats = display.stats();
+    assert_eq!(stats.write_errors, 1);
+    assert_eq!(stats.read_errors, 1);
+    assert_eq!(stats.errors(), 2);
+    assert_eq!(
+        stats.last_error,
+        Some(LastError {
+            kind: TransactionKind::Read,
+            command: 0x8b,
+            transaction: 3,
+        })
+    );
+
+    display.reset_stats();
+    assert_eq!(display.stats(), Stats::default());
+}
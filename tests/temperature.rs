@@ -0,0 +1,80 @@
+mod common;
+
+use akafugu_twidisplay::*;
+use common::RecordingBus;
+
+fn shown(tenths: i16, unit: TempUnits, display_unit: TempUnits) -> (String, u8) {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+    display
+        .display_temperature_decimal(tenths, unit, display_unit)
+        .unwrap();
+    bus.screen()
+}
+
+#[test]
+fn tenths_are_converted_with_rounding() {
+    use TempUnits::*;
+
+    assert_eq!(Celsius.convert_tenths(235, Fahrenheit), 743);
+    assert_eq!(Celsius.convert_tenths(-400, Fahrenheit), -400);
+    // -0.72F rounds away from zero, not towards it
+    assert_eq!(Celsius.convert_tenths(-4, Fahrenheit), 313);
+    assert_eq!(Celsius.convert_tenths(-182, Fahrenheit), -8);
+    // -17.78C and 37.78C
+    assert_eq!(Fahrenheit.convert_tenths(0, Celsius), -178);
+    assert_eq!(Fahrenheit.convert_tenths(1000, Celsius), 378);
+    assert_eq!(Fahrenheit.convert_tenths(320, Celsius), 0);
+    assert_eq!(Celsius.convert_tenths(i16::MAX, Fahrenheit), i16::MAX);
+    assert_eq!(Fahrenheit.convert_tenths(-123, Fahrenheit), -123);
+}
+
+#[test]
+fn one_decimal_place_with_the_sign() {
+    use TempUnits::*;
+
+    assert_eq!(shown(235, Celsius, Celsius), ("235C".into(), 0b100));
+    assert_eq!(shown(235, Celsius, Fahrenheit), ("743F".into(), 0b100));
+    assert_eq!(shown(-4, Celsius, Celsius), ("-04C".into(), 0b100));
+    assert_eq!(shown(5, Celsius, Celsius), (" 05C".into(), 0b100));
+    assert_eq!(shown(-99, Celsius, Celsius), ("-99C".into(), 0b100));
+    assert_eq!(shown(999, Celsius, Celsius), ("999C".into(), 0b100));
+}
+
+#[test]
+fn whole_degrees_outside_the_decimal_range() {
+    use TempUnits::*;
+
+    assert_eq!(shown(1000, Celsius, Celsius), ("100C".into(), 0));
+    assert_eq!(shown(1004, Celsius, Celsius), ("100C".into(), 0));
+    assert_eq!(shown(1005, Celsius, Celsius), ("101C".into(), 0));
+    assert_eq!(shown(-100, Celsius, Celsius), ("-10C".into(), 0));
+}
+
+#[test]
+fn f32_readings_are_rounded_and_checked() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+
+    display
+        .display_temperature_f32(-0.44, TempUnits::Celsius, TempUnits::Celsius)
+        .unwrap();
+    assert_eq!(bus.screen(), ("-04C".into(), 0b100));
+    display
+        .display_temperature_f32(99.96, TempUnits::Celsius, TempUnits::Celsius)
+        .unwrap();
+    assert_eq!(bus.screen(), ("100C".into(), 0));
+
+    bus.clear();
+    for value in [f32::NAN, f32::INFINITY, 3000.0, -3000.0] {
+        assert!(matches!(
+            display.display_temperature_f32(value, TempUnits::Celsius, TempUnits::Celsius),
+            Err(Error::ValueOutOfRange)
+        ));
+    }
+    assert!(bus.writes().is_empty());
+
+    display
+        .display_temperature_f32(2999.9, TempUnits::Celsius, TempUnits::Fahrenheit)
+        .unwrap();
+}
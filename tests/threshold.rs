@@ -0,0 +1,135 @@
+mod common;
+
+use akafugu_twidisplay::*;
+use common::RecordingBus;
+
+fn show(
+    display: &mut TWIDisplay<RecordingBus>,
+    bus: &RecordingBus,
+    value: i16,
+    policy: &mut ThresholdPolicy,
+) -> (String, ThresholdState) {
+    display
+        .display_temperature_with_policy(value, TempUnits::Celsius, policy)
+        .unwrap();
+    (bus.screen().0, policy.state())
+}
+
+#[test]
+fn high_state_is_left_after_hysteresis() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+    let mut policy = ThresholdPolicy::new()
+        .thresholds(Some(10), Some(60))
+        .hysteresis(2);
+
+    let mut show = |value| show(&mut display, &bus, value, &mut policy);
+    assert_eq!(show(60), (" 60C".into(), ThresholdState::Normal));
+    assert_eq!(show(61), ("-HH-".into(), ThresholdState::High));
+    assert_eq!(show(59), ("-HH-".into(), ThresholdState::High));
+    assert_eq!(show(58), (" 58C".into(), ThresholdState::Normal));
+}
+
+#[test]
+fn low_state_is_left_after_hysteresis() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+    let mut policy = ThresholdPolicy::new()
+        .thresholds(Some(10), Some(60))
+        .hysteresis(2);
+
+    let mut show = |value| show(&mut display, &bus, value, &mut policy);
+    assert_eq!(show(9), ("-LL-".into(), ThresholdState::Low));
+    assert_eq!(show(11), ("-LL-".into(), ThresholdState::Low));
+    assert_eq!(show(12), (" 12C".into(), ThresholdState::Normal));
+    assert_eq!(show(-5), ("-LL-".into(), ThresholdState::Low));
+}
+
+#[test]
+fn limits_of_the_unit_are_not_delayed() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+    let mut policy = ThresholdPolicy::new()
+        .thresholds(Some(10), Some(60))
+        .hysteresis(2);
+
+    let mut show = |value| show(&mut display, &bus, value, &mut policy);
+    assert_eq!(show(1000), ("----".into(), ThresholdState::OutOfLimits));
+    assert_eq!(show(59), (" 59C".into(), ThresholdState::Normal));
+    assert_eq!(show(-100), ("----".into(), ThresholdState::OutOfLimits));
+}
+
+#[test]
+fn custom_labels_blink() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+    let mut policy = ThresholdPolicy::new()
+        .thresholds(None, Some(30))
+        .labels(['L', 'O', ' ', ' '], ['H', 'I', ' ', ' '])
+        .blink(true);
+
+    let mut show = |value| show(&mut display, &bus, value, &mut policy);
+    assert_eq!(show(31).0, "HI  ");
+    assert_eq!(show(31).0, "    ");
+    assert_eq!(show(31).0, "HI  ");
+    assert_eq!(show(20).0, " 20C");
+    assert_eq!(show(31).0, "HI  ");
+}
+
+#[test]
+fn alarm_dot_follows_the_state() {
+    let bus = RecordingBus::new();
+    let mut display = TWIDisplay::new(bus.clone(), DEFAULT_ADDRESS);
+    let mut policy = ThresholdPolicy::new()
+        .thresholds(None, Some(30))
+        .alarm_dot(Some(3));
+
+    display.display_dots([true, false, false, false]).unwrap();
+    bus.clear();
+
+    display
+        .display_temperature_with_policy(31, TempUnits::Celsius, &mut policy)
+        .unwrap();
+    assert_eq!(bus.screen().1, 0b0001_0010);
+
+    display
+        .display_temperature_with_policy(25, TempUnits::Celsius, &mut policy)
+        .unwrap();
+    assert_eq!(bus.screen().1, 0b0000_0010);
+
+    // no change, no write
+    bus.clear();
+    display
+        .display_temperature_with_policy(25, TempUnits::Celsius, &mut policy)
+        .unwrap();
+    assert!(bus.writes().iter().all(|w| w.first() != Some(&0x85)));
+}
+
+#[test]
+fn alarm_dot_position_is_checked() {
+    let mut display = TWIDisplay::new(RecordingBus::new(), DEFAULT_ADDRESS);
+    let mut policy = ThresholdPolicy::new().alarm_dot(Some(4));
+
+    assert!(matches!(
+        display.display_measurement(5, Unit::Celsius, &mut policy),
+        Err(Error::InvalidPosition)
+    ));
+}
+
+#[test]
+fn reset_forgets_the_state() {
+    let mut display = TWIDisplay::new(RecordingBus::new(), DEFAULT_ADDRESS);
+    let mut policy = ThresholdPolicy::new()
+        .thresholds(None, Some(30))
+        .hysteresis(5);
+
+    display
+        .display_measurement(31, Unit::Celsius, &mut policy)
+        .unwrap();
+    assert_eq!(policy.state(), ThresholdState::High);
+    policy.reset();
+    display
+        .display_measurement(29, Unit::Celsius, &mut policy)
+        .unwrap();
+    assert_eq!(policy.state(), ThresholdState::Normal);
+}